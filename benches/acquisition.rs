@@ -0,0 +1,64 @@
+//! Times target acquisition for many rotators selecting among many targets, with the
+//! [`TargetableGrid`] and with every target in a single cell, so the same [`AutoTarget`]
+//! selection looks at every target as a baseline, run with `cargo bench --bench acquisition`
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_mod_lookat::{prelude::*, TargetableGrid};
+
+const ROTATORS: usize = 2000;
+const TARGETS: usize = 500;
+const FRAMES: u32 = 20;
+
+/// Spreads entities over a 1000 units wide square
+fn position(i: usize) -> Vec3 {
+    let golden = i as f32 * 0.618_034;
+    Vec3::new(
+        golden.fract() * 1000.0,
+        0.0,
+        (i as f32 / 7.0).fract() * 1000.0,
+    )
+}
+
+/// Average time per frame, with every rotator selecting targets `range` away, from a grid of
+/// cells `cell_size` units wide, or as wide as the range without one
+fn bench(range: f32, cell_size: Option<f32>) -> Duration {
+    let mut app = App::new();
+    app.insert_resource(cell_size.map_or_else(TargetableGrid::default, TargetableGrid::new))
+        .add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            RotateTowardsPlugin::new(false),
+        ));
+    for i in 0..TARGETS {
+        app.world_mut().spawn((
+            Transform::from_translation(position(i * 3 + 1)),
+            Targetable::default(),
+        ));
+    }
+    for i in 0..ROTATORS {
+        app.world_mut().spawn((
+            Transform::from_translation(position(i * 5)),
+            AutoTarget::new(range),
+        ));
+    }
+    app.update();
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    start.elapsed() / FRAMES
+}
+
+fn main() {
+    println!("{ROTATORS} rotators, {TARGETS} targets");
+    for range in [f32::INFINITY, 200.0, 50.0] {
+        // an infinitely large cell holds every target
+        println!(
+            "range {range:>6}: default cells {:?}, 10 unit cells {:?}, a single cell {:?} per frame",
+            bench(range, None),
+            bench(range, Some(10.0)),
+            bench(range, Some(f32::INFINITY))
+        );
+    }
+}
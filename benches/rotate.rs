@@ -0,0 +1,100 @@
+//! Times rotating many billboards towards a moving camera, and towards one that stands still, on
+//! a single thread and on all of them, also as children of a turned parent with a field of view,
+//! run with `cargo bench --bench rotate`
+use std::time::{Duration, Instant};
+
+use bevy::{app::TaskPoolThreadAssignmentPolicy, prelude::*, tasks::available_parallelism};
+use bevy_mod_lookat::prelude::*;
+
+const ROTATORS: usize = 50_000;
+const FRAMES: u32 = 50;
+
+/// Spreads entities over a 1000 units wide square
+fn position(i: usize) -> Vec3 {
+    let golden = i as f32 * 0.618_034;
+    Vec3::new(
+        golden.fract() * 1000.0,
+        0.0,
+        (i as f32 / 7.0).fract() * 1000.0,
+    )
+}
+
+#[derive(Component)]
+struct Camera;
+
+fn move_camera(mut camera: Single<&mut Transform, With<Camera>>, time: Res<Time>) {
+    camera.translation.x = time.elapsed_secs().sin() * 100.0;
+}
+
+/// Average time per frame, with the rotators rotated on up to `threads` threads, while the camera
+/// moves or stands still, optionally as children of a turned parent, only turning while the camera
+/// is in their field of view
+fn bench(threads: usize, with_globals: bool, moving: bool, parented: bool) -> Duration {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(TaskPoolPlugin {
+            task_pool_options: TaskPoolOptions {
+                compute: TaskPoolThreadAssignmentPolicy {
+                    min_threads: threads,
+                    max_threads: threads,
+                    percent: 1.0,
+                    on_thread_spawn: None,
+                    on_thread_destroy: None,
+                },
+                ..default()
+            },
+        }),
+        TransformPlugin,
+        RotateTowardsPlugin::new(with_globals),
+    ));
+    if moving {
+        app.add_systems(Update, move_camera);
+    }
+    let camera = app
+        .world_mut()
+        .spawn((Transform::from_xyz(0.0, 50.0, 0.0), Camera))
+        .id();
+    let parent = app
+        .world_mut()
+        .spawn(Transform::from_rotation(Quat::from_rotation_y(0.5)).with_scale(Vec3::splat(2.0)))
+        .id();
+    for i in 0..ROTATORS {
+        let mut rotator = app.world_mut().spawn((
+            Transform::from_translation(position(i)),
+            RotateTo::new(camera),
+        ));
+        if parented {
+            rotator.insert((
+                ChildOf(parent),
+                // it is always in view, but where the rotator faces is still checked
+                FieldOfView {
+                    degrees: 360.0,
+                    ..default()
+                },
+            ));
+        }
+    }
+    app.update();
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    start.elapsed() / FRAMES
+}
+
+fn main() {
+    let threads = available_parallelism();
+    println!("{ROTATORS} rotators");
+    for parented in [false, true] {
+        for moving in [true, false] {
+            for with_globals in [false, true] {
+                let serial = bench(1, with_globals, moving, parented);
+                let parallel = bench(threads, with_globals, moving, parented);
+                println!(
+                    "parented {parented:>5}, moving {moving:>5}, with_globals {with_globals:>5}: 1 thread {serial:?}, {threads} threads {parallel:?} per frame, {:.1}x",
+                    serial.as_secs_f64() / parallel.as_secs_f64()
+                );
+            }
+        }
+    }
+}
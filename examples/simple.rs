@@ -1,6 +1,6 @@
 //! A simple 3D scene with light shining over a cube sitting on a plane.
 use bevy::{camera::primitives::Aabb, color::palettes::css::*, prelude::*};
-use bevy_mod_lookat::*;
+use bevy_mod_lookat::prelude::*;
 
 fn main() {
     App::new()
@@ -108,11 +108,8 @@ fn setup(
                         Mesh3d(meshes.add(Cuboid::new(0.3, 0.1, 2.0))),
                         MeshMaterial3d(materials.add(Color::from(GREEN))),
                         Transform::from_xyz(0.0, 0.7, 0.0),
-                        RotateTo {
-                            entity: target_id,
-                            // this choses what the flat side should be in relation towards
-                            updir: UpDirection::Parent,
-                        },
+                        // the up direction choses what the flat side should be in relation towards
+                        RotateTo::new(target_id).with_up(UpDirection::Parent),
                         ShowForward,
                     ));
                 });
@@ -184,9 +181,10 @@ mod tests {
                                     ..default()
                                 },
                                 RotateTo {
-                                    entity: target_id,
+                                    target: RotateTarget::Entity(target_id),
                                     // this choses what the flat side should be in relation towards
                                     updir: UpDirection::Parent,
+                                    ..default()
                                 },
                                 ShowForward,
                             ));
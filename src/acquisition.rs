@@ -0,0 +1,567 @@
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities};
+use bevy_math::prelude::*;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::components::{ForwardAxis, RotateTarget, RotateTo};
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Marks an entity as a target an [`AutoTarget`] can select
+pub struct Targetable {
+    /// bit mask of the groups the entity is in, like enemies or allies,
+    /// matched against [`AutoTarget::filter`]
+    pub groups: u32,
+    /// how important the target is, see [`TargetSelection::HighestPriority`]
+    pub priority: f32,
+}
+
+/// In the first group, without priority
+impl Default for Targetable {
+    fn default() -> Self {
+        Self {
+            groups: 1,
+            priority: 0.0,
+        }
+    }
+}
+
+impl Targetable {
+    /// Sets how important the target is
+    pub fn with_priority(mut self, priority: f32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How an [`AutoTarget`] selects among the targets it accepts
+pub enum TargetSelection {
+    /// the nearest target, scored by its distance
+    #[default]
+    Nearest,
+    /// the target closest to where the entity is facing, scored by the angle to it in degrees,
+    /// then the nearest
+    MostCentral,
+    /// the target with the highest [`Targetable::priority`], scored by its priority,
+    /// then the nearest
+    HighestPriority,
+    /// keeps the selected target for as long as it is accepted, then selects the nearest
+    Sticky,
+}
+
+impl TargetSelection {
+    /// The score of a target, lower is better, compared by the first value, then the second
+    fn score(
+        &self,
+        targetable: &Targetable,
+        distance: f32,
+        direction: Vec3,
+        forward: Vec3,
+    ) -> (f32, f32) {
+        match self {
+            TargetSelection::Nearest | TargetSelection::Sticky => (distance, 0.0),
+            TargetSelection::MostCentral => {
+                (forward.angle_between(direction).to_degrees(), distance)
+            }
+            TargetSelection::HighestPriority => (-targetable.priority, distance),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting the best [`Targetable`] entity in range,
+/// the nearest by default, see [`TargetSelection`],
+/// so it does not have to be selected by hand, and a new one is selected when the target is despawned
+///
+/// While there is no target in range, the entity keeps its rotation.
+pub struct AutoTarget {
+    /// bit mask of the [`Targetable::groups`] that can be selected
+    pub filter: u32,
+    /// how far away targets can be selected
+    pub range: f32,
+    /// how the target is selected
+    pub selection: TargetSelection,
+    /// how much better the score of another target has to be, to replace the selected target,
+    /// so targets with about the same score do not flicker, see [`TargetSelection`]
+    pub hysteresis: f32,
+}
+
+/// Selects the nearest target of any group, at any distance
+impl Default for AutoTarget {
+    fn default() -> Self {
+        Self {
+            filter: u32::MAX,
+            range: f32::INFINITY,
+            selection: TargetSelection::Nearest,
+            hysteresis: 0.0,
+        }
+    }
+}
+
+impl AutoTarget {
+    /// Selects targets of any group, up to `range` away
+    pub fn new(range: f32) -> Self {
+        Self {
+            range,
+            ..Self::default()
+        }
+    }
+
+    /// Only selects targets in any of the `groups`
+    pub fn with_filter(mut self, groups: u32) -> Self {
+        self.filter = groups;
+        self
+    }
+
+    /// Selects the target with `selection`
+    pub fn with_selection(mut self, selection: TargetSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Only replaces the selected target with one whose score is better by more than `hysteresis`
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// If an entity in the `groups`, at `distance`, can be selected
+    pub fn accepts(&self, groups: u32, distance: f32) -> bool {
+        self.filter & groups != 0 && distance <= self.range
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A [`Targetable`] entity in the [`TargetableGrid`]
+pub struct GridTarget {
+    pub entity: Entity,
+    pub targetable: Targetable,
+    /// world space position of the entity
+    pub position: Vec3,
+}
+
+#[derive(Resource, Debug)]
+/// The [`Targetable`] entities bucketed into a grid of cubic cells by their position, rebuilt every
+/// frame there are [`AutoTarget`]s, so they only look at the targets in and near their range
+///
+/// By default the cells are as wide as the largest finite range of the [`AutoTarget`]s, so a
+/// selection looks at no more than 27 cells. Insert it with [`TargetableGrid::new`] before adding
+/// the [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) to use another cell size. When a range
+/// covers more cells than there are cells with targets, every target is looked at instead, so a
+/// selection costs about as much as it does without a grid at most.
+///
+/// In the `acquisition` benchmark, on a single core Intel Xeon VM, 2000 [`AutoTarget`]s select
+/// among 500 targets spread over 1000 units. With every target in a single infinitely large
+/// cell, which selects the same way as without a grid, a frame takes about 3ms with a range of
+/// 50, and 5ms with a range of 200. With the default cells it takes about 0.7ms and 2.5ms, with
+/// cells of 10 units about 1.5ms and 5ms.
+pub struct TargetableGrid {
+    /// the cell size given to [`TargetableGrid::new`], the cells follow the ranges without it
+    fixed_cell_size: Option<f32>,
+    cell_size: f32,
+    /// every target, to look at them all without visiting every cell
+    targets: Vec<GridTarget>,
+    cells: HashMap<IVec3, Vec<GridTarget>>,
+    /// the smallest and largest cell with targets
+    bounds: Option<(IVec3, IVec3)>,
+}
+
+/// Cells as wide as the largest range of the [`AutoTarget`]s
+impl Default for TargetableGrid {
+    fn default() -> Self {
+        Self {
+            fixed_cell_size: None,
+            ..Self::new(10.0)
+        }
+    }
+}
+
+impl TargetableGrid {
+    /// An empty grid of cells `cell_size` units wide
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            fixed_cell_size: Some(cell_size),
+            cell_size,
+            targets: Vec::new(),
+            cells: HashMap::default(),
+            bounds: None,
+        }
+    }
+
+    /// How wide the cells are
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// The cell a position is in
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / self.cell_size).floor().as_ivec3()
+    }
+
+    /// Empties the grid, and makes the cells `range` units wide, unless the grid was created
+    /// with a cell size
+    fn clear_for_range(&mut self, range: Option<f32>) {
+        self.clear();
+        if let (None, Some(range)) = (self.fixed_cell_size, range) {
+            self.cell_size = range;
+        }
+    }
+
+    /// Empties the grid
+    pub fn clear(&mut self) {
+        self.targets.clear();
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// Adds a target to the cell of its position
+    pub fn insert(&mut self, target: GridTarget) {
+        let cell = self.cell(target.position);
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(cell), max.max(cell)),
+            None => (cell, cell),
+        });
+        self.cells.entry(cell).or_default().push(target);
+        self.targets.push(target);
+    }
+
+    /// The targets in the cells within `radius` of `center`, which also includes some targets
+    /// a bit farther away, every target when `radius` is infinite
+    pub fn within(&self, center: Vec3, radius: f32) -> impl Iterator<Item = &GridTarget> {
+        let (bounds_min, bounds_max) = self.bounds.unwrap_or_default();
+        let cells = radius.is_finite().then(|| {
+            // only the cells that can have targets
+            (
+                self.cell(center - Vec3::splat(radius)).max(bounds_min),
+                self.cell(center + Vec3::splat(radius)).min(bounds_max),
+            )
+        });
+        // looking at every target is cheaper than looking up more cells than there are
+        let cells = cells.filter(|(min, max)| {
+            let size = (*max - *min + IVec3::ONE).max(IVec3::ZERO);
+            size.as_i64vec3().element_product() <= self.cells.len() as i64
+        });
+        let near = cells.into_iter().flat_map(|(min, max)| {
+            (min.x..=max.x).flat_map(move |x| {
+                (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+            })
+        });
+        let all = cells.is_none().then_some(&self.targets);
+        near.filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .chain(all.into_iter().flatten())
+    }
+}
+
+/// Rebuilds the [`TargetableGrid`] from every [`Targetable`], with cells as wide as the largest
+/// finite range of the [`AutoTarget`]s
+pub(crate) fn build_targetable_grid(
+    mut grid: ResMut<TargetableGrid>,
+    auto_targets: Query<&AutoTarget>,
+    targetables: Query<(Entity, &Targetable, &GlobalTransform)>,
+) {
+    let range = auto_targets
+        .iter()
+        .map(|auto_target| auto_target.range)
+        .filter(|range| range.is_finite() && *range > 0.0)
+        .reduce(f32::max);
+    grid.clear_for_range(range);
+    for (entity, targetable, gt) in targetables.iter() {
+        grid.insert(GridTarget {
+            entity,
+            targetable: *targetable,
+            position: gt.translation(),
+        });
+    }
+}
+
+/// Points the [`RotateTo`] of every [`AutoTarget`] at the best [`Targetable`] it accepts
+pub(crate) fn acquire_targets(
+    grid: Res<TargetableGrid>,
+    mut rotators: Query<(
+        Entity,
+        &AutoTarget,
+        &GlobalTransform,
+        Option<&ForwardAxis>,
+        &mut RotateTo,
+    )>,
+) {
+    for (rotator, auto_target, rotator_gt, forward_axis, mut rotate_to) in rotators.iter_mut() {
+        let position = rotator_gt.translation();
+        // only the angle to it is scored, so it is turned by the matrix, without decomposing it
+        let forward = rotator_gt
+            .affine()
+            .transform_vector3(*forward_axis.map_or(Dir3::NEG_Z, |axis| axis.0));
+        let selection = auto_target.selection;
+        let selected = rotate_to.target.entity();
+
+        let mut current = None;
+        let mut best: Option<(Entity, (f32, f32))> = None;
+        for &GridTarget {
+            entity,
+            targetable,
+            position: target_position,
+        } in grid.within(position, auto_target.range)
+        {
+            let direction = target_position - position;
+            let distance = direction.length();
+            if entity == rotator || !auto_target.accepts(targetable.groups, distance) {
+                continue;
+            }
+            let score = selection.score(&targetable, distance, direction, forward);
+            if Some(entity) == selected {
+                current = Some(score);
+            }
+            let better = |(_, best): (Entity, (f32, f32))| {
+                score
+                    .0
+                    .total_cmp(&best.0)
+                    .then(score.1.total_cmp(&best.1))
+                    .is_lt()
+            };
+            if best.is_none_or(better) {
+                best = Some((entity, score));
+            }
+        }
+        let target = match (selected, current, best) {
+            // keep the selected target while it is accepted
+            (Some(selected), Some(_), _) if selection == TargetSelection::Sticky => selected,
+            // unless another target is better by more than the hysteresis
+            (Some(selected), Some(current), Some((_, best)))
+                if current.0 - best.0 <= auto_target.hysteresis =>
+            {
+                selected
+            }
+            (_, _, best) => best.map_or(Entity::PLACEHOLDER, |(entity, _)| entity),
+        };
+
+        // only write on changes, a changed target restarts eased transitions
+        let target = RotateTarget::Entity(target);
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+#[derive(Clone, Component, Debug, Default, PartialEq, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting the first of its targets that is available,
+/// a point or a direction always is, an entity while it exists and has a [`GlobalTransform`]
+///
+/// Like looking at the player, else at the vehicle of the player, else at the town center.
+/// While none is available, the entity keeps its rotation.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # let (player, vehicle) = (Entity::PLACEHOLDER, Entity::PLACEHOLDER);
+/// let rotate_to_any = RotateToAny::new([
+///     RotateTarget::Entity(player),
+///     RotateTarget::Entity(vehicle),
+///     RotateTarget::Point(Vec3::new(100.0, 0.0, 50.0)),
+/// ]);
+/// ```
+pub struct RotateToAny {
+    /// the targets, in the order they are tried
+    #[entities]
+    pub targets: Vec<RotateTarget>,
+}
+
+impl RotateToAny {
+    /// Tries the `targets` in order
+    pub fn new(targets: impl IntoIterator<Item = RotateTarget>) -> Self {
+        Self {
+            targets: targets.into_iter().collect(),
+        }
+    }
+}
+
+/// Points the [`RotateTo`] of every [`RotateToAny`] at its first available target
+pub(crate) fn retarget_rotate_to_any(
+    available: Query<(), With<GlobalTransform>>,
+    mut rotators: Query<(&RotateToAny, &mut RotateTo)>,
+) {
+    for (rotate_to_any, mut rotate_to) in rotators.iter_mut() {
+        let target = rotate_to_any
+            .targets
+            .iter()
+            .copied()
+            .find(|target| {
+                target
+                    .entity()
+                    .is_none_or(|entity| available.contains(entity))
+            })
+            .unwrap_or(RotateTarget::Entity(Entity::PLACEHOLDER));
+        // only write on changes, a changed target restarts eased transitions
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+
+    use super::*;
+    use crate::RotateTowardsPlugin;
+
+    fn spawn_targetable(app: &mut App, position: Vec3, targetable: Targetable) -> Entity {
+        app.world_mut()
+            .spawn((GlobalTransform::from_translation(position), targetable))
+            .id()
+    }
+
+    fn target_of(app: &App, rotator: Entity) -> RotateTarget {
+        app.world().get::<RotateTo>(rotator).unwrap().target
+    }
+
+    #[test]
+    fn grid_finds_the_targets_near_the_center() {
+        let mut grid = TargetableGrid::new(10.0);
+        for i in 0..100 {
+            grid.insert(GridTarget {
+                entity: Entity::from_raw_u32(i).unwrap(),
+                targetable: Targetable::default(),
+                position: Vec3::new(i as f32 * 3.0, 0.0, 0.0),
+            });
+        }
+        let near = grid
+            .within(Vec3::new(30.0, 0.0, 0.0), 5.0)
+            .map(|target| target.position.x)
+            .collect::<Vec<_>>();
+        assert!(near.contains(&27.0) && near.contains(&33.0), "{near:?}");
+        assert!(!near.contains(&60.0), "{near:?}");
+        assert_eq!(grid.within(Vec3::ZERO, f32::INFINITY).count(), 100);
+        assert_eq!(grid.within(Vec3::new(0.0, 500.0, 0.0), 5.0).count(), 0);
+    }
+
+    #[test]
+    fn grid_cells_follow_the_largest_finite_range() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        for range in [20.0, 50.0, f32::INFINITY] {
+            app.world_mut()
+                .spawn((GlobalTransform::default(), AutoTarget::new(range)));
+        }
+        app.update();
+        assert_eq!(app.world().resource::<TargetableGrid>().cell_size(), 50.0);
+
+        let mut app = App::new();
+        app.insert_resource(TargetableGrid::new(5.0))
+            .add_plugins(RotateTowardsPlugin::new(false));
+        app.world_mut()
+            .spawn((GlobalTransform::default(), AutoTarget::new(50.0)));
+        app.update();
+        assert_eq!(app.world().resource::<TargetableGrid>().cell_size(), 5.0);
+    }
+
+    #[test]
+    fn auto_target_selects_the_nearest_accepted_target() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let near = spawn_targetable(&mut app, Vec3::X * 5.0, Targetable::default());
+        let far = spawn_targetable(&mut app, Vec3::Z * 8.0, Targetable::default());
+        let other_group = Targetable {
+            groups: 2,
+            ..Targetable::default()
+        };
+        spawn_targetable(&mut app, Vec3::X, other_group);
+        spawn_targetable(&mut app, Vec3::X * 20.0, Targetable::default());
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                AutoTarget::new(10.0).with_filter(1),
+            ))
+            .id();
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(near));
+
+        app.world_mut().despawn(near);
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(far));
+        app.world_mut().despawn(far);
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::default());
+    }
+
+    #[test]
+    fn target_selection_and_hysteresis() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let side = spawn_targetable(&mut app, Vec3::X * 5.0, Targetable::default());
+        let ahead = spawn_targetable(
+            &mut app,
+            Vec3::NEG_Z * 5.2,
+            Targetable::default().with_priority(1.0),
+        );
+        let mut spawn = |auto_target: AutoTarget| {
+            app.world_mut()
+                .spawn((
+                    Transform::default(),
+                    GlobalTransform::default(),
+                    auto_target,
+                ))
+                .id()
+        };
+        let hysteresis = spawn(AutoTarget::default().with_hysteresis(0.5));
+        let priority =
+            spawn(AutoTarget::default().with_selection(TargetSelection::HighestPriority));
+        let central = spawn(AutoTarget::default().with_selection(TargetSelection::MostCentral));
+        let sticky = spawn(AutoTarget::default().with_selection(TargetSelection::Sticky));
+        app.update();
+        assert_eq!(target_of(&app, hysteresis), RotateTarget::Entity(side));
+        assert_eq!(target_of(&app, priority), RotateTarget::Entity(ahead));
+        assert_eq!(target_of(&app, central), RotateTarget::Entity(ahead));
+        assert_eq!(target_of(&app, sticky), RotateTarget::Entity(side));
+
+        // closer, but not by more than the hysteresis
+        *app.world_mut().get_mut::<GlobalTransform>(ahead).unwrap() =
+            GlobalTransform::from_translation(Vec3::NEG_Z * 4.7);
+        app.update();
+        assert_eq!(target_of(&app, hysteresis), RotateTarget::Entity(side));
+        *app.world_mut().get_mut::<GlobalTransform>(ahead).unwrap() =
+            GlobalTransform::from_translation(Vec3::NEG_Z * 4.0);
+        app.update();
+        assert_eq!(target_of(&app, hysteresis), RotateTarget::Entity(ahead));
+        assert_eq!(target_of(&app, sticky), RotateTarget::Entity(side));
+    }
+
+    #[test]
+    fn rotate_to_any_falls_back_to_the_next_target() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let player = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let vehicle = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let point = RotateTarget::Point(Vec3::X);
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateToAny::new([player.into(), vehicle.into(), point]),
+            ))
+            .id();
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(player));
+        app.world_mut().despawn(player);
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(vehicle));
+        app.world_mut().despawn(vehicle);
+        app.update();
+        assert_eq!(target_of(&app, rotator), point);
+    }
+}
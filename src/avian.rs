@@ -0,0 +1,199 @@
+use avian3d::prelude::*;
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::SystemParamItem,
+};
+use bevy_math::prelude::*;
+
+use crate::{
+    components::RotateTorque,
+    line_of_sight::{LineOfSightBackend, LineOfSightPlugin},
+    velocity::{AngularVelocitySinkPlugin, TorqueSinkPlugin, VelocitySourcePlugin},
+};
+
+/// Plugin that wires the rigid bodies and colliders of [Avian](avian3d) up to the rotators, with
+/// the `avian` feature
+///
+/// - the [`LinearVelocity`] of a target is its [`TargetVelocity`](crate::TargetVelocity), that
+///   [`AimLead`](crate::AimLead) aims with
+/// - the [`RotateAngularVelocity`](crate::RotateAngularVelocity) of a rotator is written to its
+///   [`AngularVelocity`], like of a kinematic body
+/// - the torque of a [`RotateTorque`] is the [`ConstantTorque`] of its
+///   entity, like of a dynamic body
+/// - colliders block the [`LineOfSight`](crate::LineOfSight) of rotators, see [`AvianLineOfSight`]
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`RotateTowardsAvianPlugin::in_schedule`].
+///
+/// ```no_run
+/// # use avian3d::prelude::*;
+/// # use bevy_app::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// App::new().add_plugins((
+///     PhysicsPlugins::default(),
+///     RotateTowardsPlugin::default(),
+///     RotateTowardsAvianPlugin::default(),
+/// ));
+/// ```
+pub struct RotateTowardsAvianPlugin {
+    /// the schedule the bodies are synced in
+    schedule: InternedScheduleLabel,
+}
+
+impl Default for RotateTowardsAvianPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+        }
+    }
+}
+
+impl RotateTowardsAvianPlugin {
+    /// Syncs the bodies in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl Plugin for RotateTowardsAvianPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_required_components::<RotateTorque, ConstantTorque>();
+        app.add_plugins((
+            VelocitySourcePlugin::new(|velocity: &LinearVelocity| velocity.0)
+                .in_schedule(self.schedule),
+            AngularVelocitySinkPlugin::new(|body: &mut AngularVelocity, velocity| {
+                body.0 = velocity;
+            })
+            .in_schedule(self.schedule),
+            TorqueSinkPlugin::new(|body: &mut ConstantTorque, torque| body.0 = torque)
+                .in_schedule(self.schedule),
+            LineOfSightPlugin::<AvianLineOfSight>::default().in_schedule(self.schedule),
+        ));
+    }
+}
+
+/// [`LineOfSightBackend`] casting rays against the colliders of [Avian](avian3d), added by the
+/// [`RotateTowardsAvianPlugin`]
+///
+/// Every collider obstructs the ray, other than the ones on the rotator and on its target
+/// themselves, put colliders on children of them in a layer the [`SpatialQuery`] does not hit.
+pub struct AvianLineOfSight;
+
+impl LineOfSightBackend for AvianLineOfSight {
+    type Param = SpatialQuery<'static, 'static>;
+
+    fn is_clear(
+        spatial_query: &SystemParamItem<Self::Param>,
+        from: Vec3,
+        to: Vec3,
+        rotator: Entity,
+        target: Option<Entity>,
+    ) -> bool {
+        let Ok((direction, distance)) = Dir3::new_and_length(to - from) else {
+            return true;
+        };
+        let filter =
+            SpatialQueryFilter::from_excluded_entities(core::iter::once(rotator).chain(target));
+        spatial_query
+            .cast_ray(from, direction, distance, true, &filter)
+            .is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{f32::consts::PI, time::Duration};
+
+    use bevy_time::prelude::*;
+    use bevy_transform::prelude::*;
+
+    use super::*;
+    use crate::{
+        components::{RotateAngularVelocity, RotateTo, RotateTorqueState, TargetVelocity},
+        RotateTowardsPlugin,
+    };
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            RotateTowardsPlugin::new(false),
+            RotateTowardsAvianPlugin::default(),
+        ))
+        // what the spatial queries need, without stepping the simulation
+        .init_resource::<avian3d::collider_tree::ColliderTrees>();
+        app
+    }
+
+    #[test]
+    fn linear_velocity_is_the_target_velocity() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                LinearVelocity(Vec3::new(1.0, 0.0, 2.0)),
+            ))
+            .id();
+        app.update();
+        let velocity = app.world().get::<TargetVelocity>(target).unwrap();
+        assert_eq!(velocity.0, Vec3::new(1.0, 0.0, 2.0));
+    }
+
+    #[test]
+    fn angular_velocity_is_written_to_the_body() {
+        let mut app = app();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        app.insert_resource(time);
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::X),
+                RotateAngularVelocity::default(),
+                AngularVelocity::ZERO,
+            ))
+            .id();
+        app.update();
+        let velocity = app.world().get::<AngularVelocity>(rotator).unwrap().0;
+        assert_eq!(
+            velocity,
+            app.world().get::<RotateAngularVelocity>(rotator).unwrap().0
+        );
+        // a quarter turn to the right in a tenth of a second
+        assert!(velocity.abs_diff_eq(Vec3::new(0.0, -5.0 * PI, 0.0), 1e-3));
+    }
+
+    #[test]
+    fn torque_is_the_constant_torque_of_the_body() {
+        let mut app = app();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        app.insert_resource(time);
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::X),
+                RotateTorque::default(),
+            ))
+            .id();
+        app.update();
+        let torque = app.world().get::<ConstantTorque>(rotator).unwrap().0;
+        assert_eq!(
+            torque,
+            app.world()
+                .get::<RotateTorqueState>(rotator)
+                .unwrap()
+                .torque
+        );
+        // turning right
+        assert!(torque.y < 0.0, "{torque}");
+    }
+}
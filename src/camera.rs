@@ -0,0 +1,85 @@
+use bevy_camera::{prelude::*, visibility::VisibilityClass};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+
+use crate::components::{RotateTarget, RotateTo};
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting the active [`Camera3d`], so it does not have to be
+/// looked up and set by hand, and the entity keeps facing the camera when it is respawned
+///
+/// When several cameras are active, the one with the lowest [`Camera::order`] is used.
+/// While there is no camera, the entity keeps its rotation.
+pub struct LookAtCamera {
+    /// only target a camera with this [`Camera::order`], like the view of a player in split screen
+    pub camera_order: Option<isize>,
+}
+
+/// Points the [`RotateTo`] of every [`LookAtCamera`] at the camera it should face
+pub(crate) fn retarget_look_at_camera(
+    cameras: Query<(Entity, &Camera), With<Camera3d>>,
+    mut rotators: Query<(&LookAtCamera, &mut RotateTo)>,
+) {
+    for (look_at_camera, mut rotate_to) in rotators.iter_mut() {
+        let camera = cameras
+            .iter()
+            .filter(|(_, camera)| {
+                camera.is_active
+                    && look_at_camera
+                        .camera_order
+                        .is_none_or(|order| camera.order == order)
+            })
+            .min_by_key(|(_, camera)| camera.order)
+            .map_or(Entity::PLACEHOLDER, |(entity, _)| entity);
+        // only write on changes, a changed target restarts eased transitions
+        let target = RotateTarget::Entity(camera);
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+/// Skips rotating the rotators that are hidden, or not visible in any view, like off-screen
+/// billboards, so they do not cost any look-at and global transform calculations, see
+/// [`RotateTowardsPlugin::skip_invisible`](crate::RotateTowardsPlugin::skip_invisible)
+///
+/// The visibility is the one found for the last frame, so a rotator coming into view turns
+/// towards its target a frame late. Add [`AlwaysRotate`] to rotators that matter to gameplay.
+pub struct SkipInvisibleRotators;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Keeps rotating the entity while it is not visible, with [`SkipInvisibleRotators`], like an
+/// off-screen turret the gameplay depends on
+pub struct AlwaysRotate;
+
+/// What tells if a rotator is visible
+pub(crate) type RotatorVisibility = (
+    Option<&'static InheritedVisibility>,
+    Option<&'static ViewVisibility>,
+    Has<VisibilityClass>,
+    Has<AlwaysRotate>,
+);
+
+/// If the rotator is hidden, or not visible in any view, without [`AlwaysRotate`]
+pub(crate) fn is_invisible(
+    (inherited, view, checked, always): &(
+        Option<&InheritedVisibility>,
+        Option<&ViewVisibility>,
+        bool,
+        bool,
+    ),
+) -> bool {
+    if *always {
+        return false;
+    }
+    // only entities with a visibility class are checked against the views
+    let out_of_view = *checked && view.is_some_and(|view| !view.get());
+    inherited.is_some_and(|inherited| !inherited.get()) || out_of_view
+}
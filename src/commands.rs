@@ -0,0 +1,123 @@
+use bevy_ecs::prelude::*;
+use bevy_math::prelude::*;
+
+use crate::components::{RotateTarget, RotateTo, RotateToCentroid, RotateToDirection};
+
+/// Extension methods on [`EntityCommands`] for attaching, retargeting and removing rotators
+///
+/// Retargeting keeps the [`UpDirection`](crate::UpDirection) of an existing [`RotateTo`], its
+/// [`target_offset`](RotateTo::target_offset), whether it is [`enabled`](RotateTo::enabled), and
+/// the other optional components configuring the rotator.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn aim(mut commands: Commands, turret: Entity, enemy: Entity) {
+///     commands.entity(turret).look_at_entity(enemy);
+/// }
+/// ```
+pub trait RotateToCommandsExt {
+    /// Rotates the entity towards the target entity
+    fn look_at_entity(&mut self, target: Entity) -> &mut Self;
+    /// Rotates the entity towards the world space point
+    fn look_at_point(&mut self, point: Vec3) -> &mut Self;
+    /// Rotates the entity to look along the world space direction
+    fn look_along(&mut self, direction: Dir3) -> &mut Self;
+    /// Rotates the entity towards the target, keeping the current [`UpDirection`](crate::UpDirection) if it has one
+    fn look_at(&mut self, target: impl Into<RotateTarget>) -> &mut Self;
+    /// Stops rotating the entity, removing its [`RotateTo`], [`RotateToDirection`] and
+    /// [`RotateToCentroid`], it keeps its current rotation
+    fn stop_looking(&mut self) -> &mut Self;
+}
+
+impl RotateToCommandsExt for EntityCommands<'_> {
+    fn look_at_entity(&mut self, target: Entity) -> &mut Self {
+        self.look_at(target)
+    }
+
+    fn look_at_point(&mut self, point: Vec3) -> &mut Self {
+        self.look_at(point)
+    }
+
+    fn look_along(&mut self, direction: Dir3) -> &mut Self {
+        self.look_at(direction)
+    }
+
+    fn look_at(&mut self, target: impl Into<RotateTarget>) -> &mut Self {
+        let target = target.into();
+        self.queue(move |mut entity: EntityWorldMut| {
+            // retargeting keeps the rest of the settings, like whether it is enabled
+            if let Some(mut rotate_to) = entity.get_mut::<RotateTo>() {
+                rotate_to.target = target;
+                return;
+            }
+            let updir = entity
+                .get::<RotateToDirection>()
+                .map(|r| r.updir)
+                .unwrap_or_default();
+            entity.insert(RotateTo::new(target).with_up(updir));
+        })
+    }
+
+    fn stop_looking(&mut self) -> &mut Self {
+        self.try_remove::<(RotateTo, RotateToDirection, RotateToCentroid)>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::UpDirection;
+
+    #[test]
+    fn look_at_keeps_a_disabled_rotator_disabled() {
+        let mut world = World::new();
+        let mut rotate_to = RotateTo::new(Vec3::X).with_up_dir(Dir3::Z);
+        rotate_to.enabled = false;
+        let rotator = world.spawn(rotate_to).id();
+        world.commands().entity(rotator).look_at(Vec3::Y);
+        world.flush();
+        let rotate_to = world.get::<RotateTo>(rotator).unwrap();
+        assert_eq!(rotate_to.target, RotateTarget::Point(Vec3::Y));
+        assert!(!rotate_to.enabled);
+        assert_eq!(rotate_to.updir, UpDirection::Dir(Dir3::Z));
+    }
+
+    #[test]
+    fn look_at_keeps_the_target_offset() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let offset = Vec3::Y * 1.6;
+        let rotator = world
+            .spawn(RotateTo::new(first).with_target_offset(offset))
+            .id();
+        world.commands().entity(rotator).look_at_entity(second);
+        world.flush();
+        let rotate_to = world.get::<RotateTo>(rotator).unwrap();
+        assert_eq!(rotate_to.target, RotateTarget::Entity(second));
+        assert_eq!(rotate_to.target_offset, offset);
+    }
+
+    #[test]
+    fn stop_looking_removes_every_target() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let rotators = [
+            world.spawn(RotateTo::new(target)).id(),
+            world.spawn(RotateToDirection::new(Dir3::X)).id(),
+            world.spawn(RotateToCentroid::new([target])).id(),
+        ];
+        for rotator in rotators {
+            world.commands().entity(rotator).stop_looking();
+        }
+        world.flush();
+        for rotator in rotators {
+            let entity = world.entity(rotator);
+            assert!(!entity.contains::<RotateTo>());
+            assert!(!entity.contains::<RotateToDirection>());
+            assert!(!entity.contains::<RotateToCentroid>());
+        }
+    }
+}
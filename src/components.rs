@@ -0,0 +1,2301 @@
+use core::f32::consts::TAU;
+
+use bevy_ecs::{
+    entity::{EntityHashSet, MapEntities},
+    lifecycle::HookContext,
+    prelude::*,
+    reflect::ReflectMapEntities,
+    world::DeferredWorld,
+};
+#[cfg(feature = "bevy-inspector-egui")]
+use bevy_inspector_egui::{inspector_options::std_options::NumberDisplay, prelude::*};
+use bevy_math::{curve::EaseFunction, ops, prelude::*, StableInterpolate};
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    events::{RotateTargetMissing, TargetLost},
+    math::{calculate_angular_velocity, calculate_intercept_time, swing_twist},
+    systems::{warn_missing_target, LostTargets, RotateCache, RotateSolution},
+};
+
+#[derive(Clone, Component, Debug, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateCache)]
+#[component(on_insert = insert_rotate_to, on_replace = replace_rotate_to)]
+/// When this component is added on an entity, [`Transform::forward()`] direction points towards the selected
+/// target always
+///
+/// When inserted, a targeted entity that does not exist is reported right away, according to the
+/// [`MissingTargetPolicy`] of the rotator, instead of when the rotation systems first run
+///
+/// Spawned from a scene, a targeted entity of the same scene is mapped to the entity spawned for
+/// it before the component is inserted, so the target is found, while a targeted entity outside
+/// the scene is reported as missing. With the `bevy_scene` feature, a target that still has no
+/// [`GlobalTransform`] once the scene instance is ready is reported then. A rotator saved without
+/// a target yet keeps targeting [`Entity::PLACEHOLDER`].
+///
+/// Rotators targeting each other in a cycle, like two entities targeting each other, each rotate
+/// from where the others were before any of them was rotated that time, so the order they are
+/// rotated in does not matter, and their entities are named in a warning once. Only rotators
+/// within the hierarchy of each other are rotated one after the other, from the top down.
+pub struct RotateTo {
+    /// what to target, see [`RotateTarget`]
+    #[entities]
+    pub target: RotateTarget,
+    /// The rotated entity will match its [`Transform::up()`] according to this
+    #[entities]
+    pub updir: UpDirection,
+    /// point to aim at, in the local space of a [`RotateTarget::Entity`], like the chest of a
+    /// character whose origin is at its feet, ignored for the other targets
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub target_offset: Vec3,
+    /// while `false`, the entity keeps its rotation, without losing the rest of the settings,
+    /// or moving it to another archetype, like removing the component would
+    #[cfg_attr(feature = "serde", serde(default = "enabled_by_default"))]
+    pub enabled: bool,
+}
+
+/// Rotators saved before they could be disabled are enabled
+#[cfg(feature = "serde")]
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Default for RotateTo {
+    fn default() -> Self {
+        Self::new(RotateTarget::default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What a [`RotateTo`] makes the entity rotate towards
+pub enum RotateTarget {
+    /// entity to target, the Targeted entity must have a [`GlobalTransform`]
+    Entity(Entity),
+    /// world space position to target, useful for targets that are not backed by an entity,
+    /// like a raycast hit or a waypoint
+    Point(Vec3),
+    /// world space direction to look along, like [`Transform::look_to`]
+    Direction(#[cfg_attr(feature = "serde", serde(with = "dir3_serde"))] Dir3),
+}
+
+impl RotateTo {
+    /// Rotates towards the target, which can be an [`Entity`], a [`Vec3`] point or a [`Dir3`] direction,
+    /// with the up direction synchronized to the target
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_mod_lookat::prelude::*;
+    /// # let target = Entity::PLACEHOLDER;
+    /// let rotate_to = RotateTo::new(target).with_up(UpDirection::Parent);
+    /// let rotate_to = RotateTo::new(Vec3::new(1.0, 2.0, 3.0)).with_up_dir(Dir3::Z);
+    /// ```
+    pub fn new(target: impl Into<RotateTarget>) -> Self {
+        Self {
+            target: target.into(),
+            updir: UpDirection::default(),
+            target_offset: Vec3::ZERO,
+            enabled: true,
+        }
+    }
+
+    /// Selects how the up direction of the rotated entity is decided
+    pub fn with_up(mut self, updir: UpDirection) -> Self {
+        self.updir = updir;
+        self
+    }
+
+    /// Keeps the up direction of the rotated entity as close to `up` as possible, see [`UpDirection::Dir`]
+    pub fn with_up_dir(self, up: Dir3) -> Self {
+        self.with_up(UpDirection::Dir(up))
+    }
+
+    /// Aims at `offset` in the local space of the target entity, instead of at its origin
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_mod_lookat::prelude::*;
+    /// # let character = Entity::PLACEHOLDER;
+    /// // aim at the head, rather than the feet
+    /// let rotate_to = RotateTo::new(character).with_target_offset(Vec3::Y * 1.6);
+    /// ```
+    pub fn with_target_offset(mut self, offset: Vec3) -> Self {
+        self.target_offset = offset;
+        self
+    }
+
+    /// The world space point aimed at on the target entity with the [`GlobalTransform`] `target_gt`
+    pub fn aim_point(&self, target_gt: &GlobalTransform) -> Vec3 {
+        target_gt.transform_point(self.target_offset)
+    }
+}
+
+/// Relates the rotator to the entity targeted by an inserted [`RotateTo`], through
+/// [`RotatingTo`], and validates the target
+fn insert_rotate_to(mut world: DeferredWorld, context: HookContext) {
+    let rotator = context.entity;
+    if let Some(target) = world
+        .get::<RotateTo>(rotator)
+        .and_then(|rotate_to| rotate_to.target.entity())
+    {
+        world.commands().queue(move |world: &mut World| {
+            // a missing target is reported instead
+            let targeted = world
+                .get::<RotateTo>(rotator)
+                .is_some_and(|rotate_to| rotate_to.target == RotateTarget::Entity(target));
+            if targeted && world.get_entity(target).is_ok() {
+                world.entity_mut(rotator).insert(RotatingTo(target));
+            }
+        });
+    }
+    validate_rotate_to_target(world, context);
+}
+
+/// Unrelates the rotator from the entity targeted by a [`RotateTo`] that is replaced or removed
+fn replace_rotate_to(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    if world.get::<RotatingTo>(entity).is_some() {
+        world.commands().entity(entity).try_remove::<RotatingTo>();
+    }
+}
+
+/// Checks the entity targeted by a newly inserted [`RotateTo`] exists
+///
+/// A target that exists but has no [`GlobalTransform`] yet, is likely still being built by the
+/// commands or scene spawning it, it is left to the rotation systems to report it if it stays that way,
+/// or to the validation of the scene once it is spawned, with the `bevy_scene` feature
+fn validate_rotate_to_target(world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(target) = world
+        .get::<RotateTo>(entity)
+        .and_then(|rotate_to| rotate_to.target.entity())
+    else {
+        return;
+    };
+    if world.get_entity(target).is_ok() {
+        return;
+    }
+    report_missing_target(world, entity, target);
+}
+
+/// Reports the `target` of a rotator as missing, unless it already is
+pub(crate) fn report_missing_target(mut world: DeferredWorld, entity: Entity, target: Entity) {
+    let policy = world
+        .get::<MissingTargetPolicy>(entity)
+        .or(world.get_resource::<MissingTargetPolicy>())
+        .copied()
+        .unwrap_or_default();
+    // without the plugin nothing rotates, so there is nothing to report
+    let Some(mut lost) = world.get_resource_mut::<LostTargets>() else {
+        return;
+    };
+    if !lost.0.insert(entity) {
+        return;
+    }
+    if policy == MissingTargetPolicy::WarnOnce {
+        warn_missing_target(entity, target);
+    }
+    world.trigger(TargetLost { entity, target });
+}
+
+/// Targets [`Entity::PLACEHOLDER`], which counts as not having selected a target yet,
+/// so the component can be added, from an editor for example, before the target is known
+impl Default for RotateTarget {
+    fn default() -> Self {
+        RotateTarget::Entity(Entity::PLACEHOLDER)
+    }
+}
+
+impl From<Entity> for RotateTarget {
+    fn from(entity: Entity) -> Self {
+        RotateTarget::Entity(entity)
+    }
+}
+
+impl From<Vec3> for RotateTarget {
+    fn from(point: Vec3) -> Self {
+        RotateTarget::Point(point)
+    }
+}
+
+impl From<Dir3> for RotateTarget {
+    fn from(direction: Dir3) -> Self {
+        RotateTarget::Direction(direction)
+    }
+}
+
+/// Maps the targeted entity, like when spawning a scene, leaving [`Entity::PLACEHOLDER`] as it is,
+/// so a rotator saved before it selected a target still has not selected one once spawned
+impl MapEntities for RotateTarget {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        if let RotateTarget::Entity(entity) = self {
+            if *entity != Entity::PLACEHOLDER {
+                *entity = entity_mapper.get_mapped(*entity);
+            }
+        }
+    }
+}
+
+impl RotateTarget {
+    /// The targeted entity, if any, [`Entity::PLACEHOLDER`] is not considered a target
+    pub fn entity(&self) -> Option<Entity> {
+        match self {
+            RotateTarget::Entity(entity) if *entity != Entity::PLACEHOLDER => Some(*entity),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Component, Debug, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateCache)]
+/// When this component is added on an entity, [`Transform::forward()`] direction points along the selected
+/// world space direction always, like [`Transform::look_to`]
+///
+/// Useful for decals and arrows that should follow some direction, like the wind, which can then be
+/// written to [`RotateToDirection::direction`] every frame.
+/// If the entity also has a [`RotateTo`], that one takes precedence.
+pub struct RotateToDirection {
+    /// world space direction to look along
+    #[cfg_attr(feature = "serde", serde(with = "dir3_serde"))]
+    pub direction: Dir3,
+    /// The rotated entity will match its [`Transform::up()`] according to this,
+    /// since a direction has no up direction of its own, [`UpDirection::Target`] falls back to Vec3::Y
+    #[entities]
+    pub updir: UpDirection,
+}
+
+#[derive(Clone, Component, Debug, Default, PartialEq, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateCache)]
+/// When this component is added on an entity, [`Transform::forward()`] direction points towards the
+/// weighted average position of several entities, like a camera framing a squad, or a boss glaring
+/// at its attackers
+///
+/// Entities that do not exist, or have no [`GlobalTransform`], are left out, while none of them
+/// can be found, the entity keeps its rotation.
+/// If the entity also has a [`RotateTo`] or a [`RotateToDirection`], those take precedence.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # let (leader, follower) = (Entity::PLACEHOLDER, Entity::PLACEHOLDER);
+/// let rotate_to_centroid = RotateToCentroid::new([leader, follower]).with_weight(leader, 2.0);
+/// ```
+pub struct RotateToCentroid {
+    /// the entities to look at the average position of
+    #[entities]
+    pub targets: Vec<CentroidTarget>,
+    /// The rotated entity will match its [`Transform::up()`] according to this,
+    /// since a centroid has no up direction of its own, [`UpDirection::Target`] falls back to Vec3::Y
+    #[entities]
+    pub updir: UpDirection,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, MapEntities)]
+#[reflect(Debug, PartialEq, MapEntities)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An entity of a [`RotateToCentroid`], and how much its position counts
+pub struct CentroidTarget {
+    #[entities]
+    pub entity: Entity,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    pub weight: f32,
+}
+
+impl RotateToCentroid {
+    /// Looks at the average position of the `targets`, weighted the same
+    pub fn new(targets: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            targets: targets
+                .into_iter()
+                .map(|entity| CentroidTarget {
+                    entity,
+                    weight: 1.0,
+                })
+                .collect(),
+            updir: UpDirection::default(),
+        }
+    }
+
+    /// Sets how much the position of `entity` counts, adding it to the targets if it is not one yet
+    pub fn with_weight(mut self, entity: Entity, weight: f32) -> Self {
+        match self.targets.iter_mut().find(|t| t.entity == entity) {
+            Some(target) => target.weight = weight,
+            None => self.targets.push(CentroidTarget { entity, weight }),
+        }
+        self
+    }
+
+    /// Selects how the up direction of the rotated entity is decided
+    pub fn with_up(mut self, updir: UpDirection) -> Self {
+        self.updir = updir;
+        self
+    }
+
+    /// Keeps the up direction of the rotated entity as close to `up` as possible, see [`UpDirection::Dir`]
+    pub fn with_up_dir(self, up: Dir3) -> Self {
+        self.with_up(UpDirection::Dir(up))
+    }
+
+    /// The weighted average of the positions of the targets that `position` finds,
+    /// `None` if it finds none of them, or their weights add up to zero
+    pub fn centroid(&self, position: impl Fn(Entity) -> Option<Vec3>) -> Option<Vec3> {
+        let (sum, total_weight) = self
+            .targets
+            .iter()
+            .filter_map(|target| Some((position(target.entity)?, target.weight)))
+            .fold((Vec3::ZERO, 0.0), |(sum, total), (position, weight)| {
+                (sum + position * weight, total + weight)
+            });
+        (total_weight != 0.0).then(|| sum / total_weight)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Limits how fast the entity rotates towards its target, instead of snapping to face it every frame
+///
+/// The limit is applied using the delta of [`Time`](bevy_time::prelude::Time), so it is frame rate independent,
+/// the entity still converges on the target as long as the target moves slower than the limit.
+pub struct RotateSpeedLimit {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// the maximum number of degrees the entity can turn each second
+    pub max_degrees_per_second: f32,
+}
+
+impl Default for RotateSpeedLimit {
+    fn default() -> Self {
+        Self {
+            max_degrees_per_second: 180.0,
+        }
+    }
+}
+
+impl RotateSpeedLimit {
+    /// Rotates `current` towards `desired`, turning no more than allowed during `delta_secs`
+    pub fn limit(&self, current: Quat, desired: Quat, delta_secs: f32) -> Quat {
+        let max_angle = self.max_degrees_per_second.to_radians() * delta_secs;
+        current.rotate_towards(desired, max_angle)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSmoothingState)]
+/// Smooths how the entity rotates towards its target, instead of snapping to face it every frame
+///
+/// Smoothing is applied before an eventual [`RotateSpeedLimit`], so both can be combined.
+pub enum RotateSmoothing {
+    /// Slerps the current rotation towards the rotation facing the target every frame,
+    /// frame rate independently, see [`StableInterpolate::smooth_nudge`]
+    Exponential {
+        /// how fast the remaining angle to the target decays, higher is faster,
+        /// a decay rate of `ln(10)` leaves a tenth of the angle after one second
+        decay_rate: f32,
+    },
+    /// Pulls the rotation towards the target with a damped angular spring,
+    /// the angular velocity is kept in [`RotateSmoothingState`]
+    Spring {
+        /// how hard the spring pulls towards the target, per radian of remaining angle
+        stiffness: f32,
+        /// how much of the angular velocity is resisted, `2 * sqrt(stiffness)` is critically damped,
+        /// lower values overshoot the target before settling
+        damping: f32,
+    },
+    /// Transitions from the rotation the entity had when it was (re)targeted, towards the target,
+    /// shaped by an easing curve, so it can for example whip around quickly and then settle softly
+    ///
+    /// A new transition starts whenever the [`RotateTo`] or [`RotateToDirection`] component changes
+    Eased {
+        /// shape of the transition, sampled from 0 to 1 over the duration
+        ease: EaseFunction,
+        /// how many seconds the transition takes
+        duration: f32,
+    },
+}
+
+impl Default for RotateSmoothing {
+    fn default() -> Self {
+        RotateSmoothing::Exponential { decay_rate: 10.0 }
+    }
+}
+
+impl RotateSmoothing {
+    /// A [`RotateSmoothing::Spring`] that settles as fast as possible without overshooting
+    pub fn critically_damped(stiffness: f32) -> Self {
+        RotateSmoothing::Spring {
+            stiffness,
+            damping: 2.0 * stiffness.sqrt(),
+        }
+    }
+
+    /// Smooths `current` towards `desired` over `delta_secs`,
+    /// `retargeted` signals that the target changed since the last frame
+    pub fn smooth(
+        &self,
+        current: Quat,
+        desired: Quat,
+        state: &mut RotateSmoothingState,
+        delta_secs: f32,
+        retargeted: bool,
+    ) -> Quat {
+        match *self {
+            RotateSmoothing::Exponential { decay_rate } => {
+                let mut rotation = current;
+                rotation.smooth_nudge(&desired, decay_rate, delta_secs);
+                rotation
+            }
+            RotateSmoothing::Spring { stiffness, damping } => {
+                let mut error = desired * current.inverse();
+                // take the shortest way around
+                if error.w < 0.0 {
+                    error = -error;
+                }
+                let acceleration =
+                    stiffness * error.to_scaled_axis() - damping * state.angular_velocity;
+                // semi-implicit euler, the velocity is updated before it is used
+                state.angular_velocity += acceleration * delta_secs;
+                (Quat::from_scaled_axis(state.angular_velocity * delta_secs) * current).normalize()
+            }
+            RotateSmoothing::Eased { ease, duration } => {
+                if retargeted {
+                    state.transition_start = current;
+                    state.transition_elapsed = 0.0;
+                }
+                state.transition_elapsed += delta_secs;
+                let t = if duration > 0.0 {
+                    state.transition_elapsed / duration
+                } else {
+                    1.0
+                };
+                state
+                    .transition_start
+                    .slerp(desired, ease.sample_clamped(t))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateSmoothing`] needs to carry between frames
+pub struct RotateSmoothingState {
+    /// angular velocity of the rotator, as a scaled axis in the local space of the rotator's parent,
+    /// in radians per second
+    pub angular_velocity: Vec3,
+    /// local rotation the current [`RotateSmoothing::Eased`] transition started from
+    pub transition_start: Quat,
+    /// seconds since the current [`RotateSmoothing::Eased`] transition started
+    pub transition_elapsed: f32,
+}
+
+#[derive(Clone, Copy, Component, Resource, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Resource, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What happens when the entity targeted by a [`RotateTo`] is despawned or has no [`GlobalTransform`]
+///
+/// Despawning a targeted entity releases its rotators right away, through its [`RotatedToBy`],
+/// triggering [`TargetLost`] on them. The ones that keep their [`RotateTo`] have their target
+/// cleared, like with [`MissingTargetPolicy::ClearTarget`], so no rotator keeps targeting an entity
+/// that no longer exists, and [`MissingTargetPolicy::WarnOnce`] does not warn about it.
+///
+/// The plugin wide default is set with [`RotateTowardsPlugin::missing_target_policy`](crate::RotateTowardsPlugin::missing_target_policy), and can be
+/// overridden per rotator by inserting this as a component on it
+pub enum MissingTargetPolicy {
+    /// Logs a warning when the target is found missing, and again only if it is lost after
+    /// having been found, the same times [`TargetLost`] is triggered
+    #[default]
+    WarnOnce,
+    /// Silently keeps the current rotation until the target can be found
+    Ignore,
+    /// Removes the [`RotateTo`] component from the rotator
+    RemoveComponent,
+    /// Clears the target of the [`RotateTo`], leaving it targeting [`Entity::PLACEHOLDER`] with
+    /// the rest of its settings, so the rotator keeps its rotation until a new target is selected,
+    /// also for a targeted entity that exists but has no [`GlobalTransform`]
+    ClearTarget,
+    /// Despawns the rotator
+    DespawnRotator,
+    /// Triggers a [`RotateTargetMissing`] event on the rotator, every time the target is found missing,
+    /// and once when it is despawned
+    EmitEvent,
+}
+
+#[derive(Clone, Copy, Component, Resource, Debug, PartialEq, Reflect)]
+#[reflect(Component, Resource, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How far apart positions, directions and rotations can be while they count as the same, for the
+/// [`CoLocatedTarget`] of a rotator, and for a rotator facing its target, which is not rotated
+/// again until something it depends on moves further than this
+///
+/// Defaults to a ten thousandth, larger suits huge worlds, where the float precision jitters, and
+/// smaller suits tiny props. The plugin wide default is set with
+/// [`RotateTowardsPlugin::epsilon`](crate::RotateTowardsPlugin::epsilon), and can be overridden
+/// per rotator by inserting this as a component on it.
+pub struct RotateEpsilon(
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))] pub f32,
+);
+
+impl Default for RotateEpsilon {
+    fn default() -> Self {
+        Self(1e-4)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Keeps the rotation of the entity while its forward direction points at its target within this
+/// angle, rotating it again once the target moves further away
+///
+/// Useful for billboards facing a slightly shaky camera, which otherwise turn a tiny bit every
+/// frame, and change their [`Transform`] for all that depend on it. Once outside the deadband the
+/// entity turns all the way to face its target again.
+pub struct RotateDeadband {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 180.0, display = NumberDisplay::Slider))]
+    /// the largest angle, in degrees, between the forward direction and the direction facing the
+    /// target, where the entity is not rotated
+    pub degrees: f32,
+}
+
+impl Default for RotateDeadband {
+    fn default() -> Self {
+        Self { degrees: 0.5 }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(AlignmentState, RotateSolution)]
+/// Triggers [`Aligned`](crate::Aligned) on the entity the frame its forward direction first points at its target,
+/// within this threshold, and optionally [`AlignmentLost`](crate::AlignmentLost) once it no longer does
+///
+/// Useful with [`RotateSmoothing`] or [`RotateSpeedLimit`], where the entity takes a while to face its target
+pub struct AlignmentThreshold {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 180.0, display = NumberDisplay::Slider))]
+    /// the largest angle, in degrees, between the forward direction and the direction facing the target,
+    /// that counts as aligned
+    pub degrees: f32,
+    /// whether to also trigger [`AlignmentLost`](crate::AlignmentLost)
+    pub trigger_lost: bool,
+}
+
+impl Default for AlignmentThreshold {
+    fn default() -> Self {
+        Self {
+            degrees: 1.0,
+            trigger_lost: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// If an entity with an [`AlignmentThreshold`] is currently aligned with its target
+pub struct AlignmentState {
+    pub aligned: bool,
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSolution)]
+/// Insert on a rotator to have the plugin keep track of how far its forward direction is from
+/// pointing at its target, after it has been rotated for the frame
+///
+/// Useful to gate firing, play servo audio or drive UI, without recomputing the look rotation.
+pub struct AngleToTarget {
+    /// the angle, in degrees, between the forward direction and the direction facing the target
+    pub degrees: f32,
+    /// signed part of the angle around the up direction of the rotator, in degrees,
+    /// positive when the target is to the left, like [`Transform::rotate_y`]
+    pub yaw_degrees: f32,
+    /// signed part of the angle around the right direction of the rotator, in degrees,
+    /// positive when the target is above
+    pub pitch_degrees: f32,
+}
+
+impl AngleToTarget {
+    /// Calculates the angles for an entity with the `current` rotation, that would face its target
+    /// with the `desired` rotation, both in the same space
+    pub fn new(current: Quat, desired: Quat) -> Self {
+        // the desired forward direction, as seen from the current rotation
+        let forward = current.inverse() * (desired * Vec3::NEG_Z);
+        Self {
+            degrees: forward.angle_between(Vec3::NEG_Z).to_degrees(),
+            yaw_degrees: ops::atan2(-forward.x, -forward.z).to_degrees(),
+            pitch_degrees: ops::asin(forward.y.clamp(-1.0, 1.0)).to_degrees(),
+        }
+    }
+
+    /// Calculates the angles for a [`Rotate2D`] entity with the `current` rotation, that would face
+    /// its target with the `desired` rotation, all of it is yaw around Z, positive counterclockwise
+    pub fn new_2d(current: Quat, desired: Quat) -> Self {
+        let forward = (current.inverse() * desired) * Vec3::X;
+        let yaw_degrees = ops::atan2(forward.y, forward.x).to_degrees();
+        Self {
+            degrees: yaw_degrees.abs(),
+            yaw_degrees,
+            pitch_degrees: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Makes a [`RotateTo`] targeting an entity, like a camera, point the entity along the back direction
+/// of the target, instead of towards its position, so it faces the plane the target is looking at
+///
+/// Billboards facing the camera this way do not shear at the edges of the screen, and face
+/// orthographic cameras properly. Targets that are not entities are not affected.
+pub struct ScreenAlignedBillboard;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Makes a [`RotateTo`] or [`RotateToDirection`] only turn the entity around its selected
+/// [`UpDirection`], keeping it upright instead of pitching it up or down towards the target
+///
+/// Useful for trees, grass cards and world space labels facing the camera. Can be combined with
+/// [`ScreenAlignedBillboard`]. While the target is straight above or below, the rotation is kept.
+pub struct CylindricalBillboard;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Makes the entity face directly away from its target instead of towards it, like a shield,
+/// an exhaust or the face of a fleeing character
+///
+/// The up direction is selected the same as when facing the target, and the [`FieldOfView`] and
+/// limits apply to the direction facing away.
+pub struct RotateAway;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Rotates the entity from the [`GlobalTransform`]s of the last transform propagation, even while
+/// the [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) calculates new global transforms,
+/// without walking the hierarchy of the entity every time it is rotated
+///
+/// For rotators that are fine with rotating a frame behind, like the distant billboards of a
+/// crowd, while the rest of them, like the camera rig, do not lag behind.
+pub struct UsePropagatedTransforms;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Makes a [`RotateTo`] or [`RotateToDirection`] only rotate the entity around Z, towards the XY
+/// position of its target, for sprites and other entities in 2D
+///
+/// The [`UpDirection`] of the rotator is not used, the Z axis is always kept pointing at the camera
+pub struct Rotate2D {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = -180.0, max = 180.0, display = NumberDisplay::Slider))]
+    /// the direction the art faces when not rotated, in degrees counterclockwise from `Vec3::X`,
+    /// so art facing up uses 90
+    pub angle_offset_degrees: f32,
+}
+
+/// Looks along [`Dir3::NEG_Z`], the default forward direction
+impl Default for RotateToDirection {
+    fn default() -> Self {
+        Self::new(Dir3::NEG_Z)
+    }
+}
+
+impl RotateToDirection {
+    /// Looks along the direction, with the up direction falling back to Vec3::Y
+    pub fn new(direction: Dir3) -> Self {
+        Self {
+            direction,
+            updir: UpDirection::default(),
+        }
+    }
+
+    /// Selects how the up direction of the rotated entity is decided
+    pub fn with_up(mut self, updir: UpDirection) -> Self {
+        self.updir = updir;
+        self
+    }
+
+    /// Keeps the up direction of the rotated entity as close to `up` as possible, see [`UpDirection::Dir`]
+    pub fn with_up_dir(self, up: Dir3) -> Self {
+        self.with_up(UpDirection::Dir(up))
+    }
+}
+
+/// Relationship kept in sync with [`RotateTo`], while it targets an entity, so that the targeted
+/// entity knows what rotates towards it through [`RotatedToBy`]
+///
+/// Inserting, replacing or removing the [`RotateTo`] updates it right away. Changing the target of
+/// the [`RotateTo`] in place updates it in the schedule of the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin), after
+/// [`RotateTowardsSystems::Retarget`](crate::RotateTowardsSystems::Retarget), even while its
+/// systems are paused or skipped by their run conditions, so it matches the [`RotateTo`] by the
+/// time the rotators are rotated.
+///
+/// Despawning the target removes the relationship, and releases the rotator according to its
+/// [`MissingTargetPolicy`]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[relationship(relationship_target = RotatedToBy)]
+pub struct RotatingTo(pub Entity);
+
+#[derive(Component, Debug, Reflect)]
+#[relationship_target(relationship = RotatingTo)]
+#[component(on_despawn = release_rotators)]
+/// The rotators whose [`RotateTo`] targets the entity, kept in sync through [`RotatingTo`], to ask what
+/// rotates towards it, like which guards are looking at the player
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn detected(player: Single<Option<&RotatedToBy>, With<Player>>) -> bool {
+///     player.is_some_and(|watchers| !watchers.is_empty())
+/// }
+/// ```
+pub struct RotatedToBy(EntityHashSet);
+
+impl RotatedToBy {
+    /// If the `rotator` rotates towards the entity
+    pub fn contains(&self, rotator: Entity) -> bool {
+        self.0.contains(&rotator)
+    }
+}
+
+/// Releases the rotators of a despawned target, according to their [`MissingTargetPolicy`]
+fn release_rotators(mut world: DeferredWorld, HookContext { entity: target, .. }: HookContext) {
+    // without the plugin nothing rotates, so there is nothing to release
+    let Some(&default_policy) = world.get_resource::<MissingTargetPolicy>() else {
+        return;
+    };
+    let Some(rotated_to_by) = world.get::<RotatedToBy>(target) else {
+        return;
+    };
+    let rotators = rotated_to_by.iter().collect::<Vec<_>>();
+    for rotator in rotators {
+        // unless it was retargeted since the relationship was synced
+        let targeted = world
+            .get::<RotateTo>(rotator)
+            .is_some_and(|rotate_to| rotate_to.target == RotateTarget::Entity(target));
+        if !targeted {
+            continue;
+        }
+        let policy = world
+            .get::<MissingTargetPolicy>(rotator)
+            .copied()
+            .unwrap_or(default_policy);
+        match policy {
+            MissingTargetPolicy::RemoveComponent => {
+                world.commands().entity(rotator).try_remove::<RotateTo>();
+            }
+            MissingTargetPolicy::DespawnRotator => {
+                world.commands().entity(rotator).try_despawn();
+            }
+            _ => {
+                if let Some(mut rotate_to) = world.get_mut::<RotateTo>(rotator) {
+                    rotate_to.target = RotateTarget::default();
+                }
+            }
+        }
+        if policy == MissingTargetPolicy::EmitEvent {
+            world.trigger(RotateTargetMissing {
+                entity: rotator,
+                target,
+            });
+        }
+        let lost = world
+            .get_resource_mut::<LostTargets>()
+            .is_some_and(|mut lost| lost.0.remove(&rotator));
+        if !lost {
+            world.trigger(TargetLost {
+                entity: rotator,
+                target,
+            });
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Default, MapEntities)]
+#[reflect(Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The rotated entity will try to have its [`Transform::up()`] direction matching this selection
+pub enum UpDirection {
+    /// Will synchronize the direction of UP towards the UP direction of the target
+    /// Useful when rotating towards the camera and wanting the direction to be up for example
+    #[default]
+    Target,
+    /// Keeps the up-direction the same as for the parent of this entity
+    /// useful when you want it rotated in relation to what this entity is attached to
+    /// Note: if there is no parent, the up direction will fallback to be Vec3::Y
+    Parent,
+    /// Keeps a static direction of UP set to this value
+    /// useful when you want to decide what is up for the entity under rotation
+    Dir(#[cfg_attr(feature = "serde", serde(with = "dir3_serde"))] Dir3),
+    /// Keeps the up-direction the same as for another entity,
+    /// useful when it should follow something else than the target or parent, like the root of a camera rig
+    /// Note: if the entity does not exist or has no [`GlobalTransform`], the up direction will fallback to be Vec3::Y
+    Entity(#[entities] Entity),
+    /// Points the up-direction towards another entity, as close as possible while looking at the target,
+    /// useful for aiming with two targets, like panels orbiting something
+    /// Note: if the entity does not exist or has no [`GlobalTransform`], the up direction will fallback to be Vec3::Y
+    TowardEntity(#[entities] Entity),
+    /// Points the up-direction away from a world space position,
+    /// useful for billboards and characters on a small planet, with the center of the planet as the position
+    /// Note: at the position itself, the up direction will fallback to be Vec3::Y
+    AwayFromPoint(Vec3),
+    /// Keeps the up-direction along the normal of the surface beneath this entity, sampled by its
+    /// [`SurfaceNormal`](crate::SurfaceNormal), useful for ground units staying aligned to slopes
+    /// Note: without a surface beneath it, the up direction will fallback to be Vec3::Y
+    SurfaceNormal,
+    /// Points the up-direction along the [`TargetVelocity`] of this entity,
+    /// useful when its motion should be the roll reference, like for missiles and birds
+    /// Note: while the entity does not move, the up direction will fallback to be Vec3::Y
+    Velocity,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The local direction of the model that is pointed at the target, for models not facing
+/// [`Transform::forward()`], like glTF exports facing `Vec3::Z` or `Vec3::X`
+///
+/// Defaults to `Dir3::NEG_Z`, the rotation of the entity is compensated for any other axis,
+/// keeping the local `Vec3::Y` of the model as its up direction when the axis is horizontal.
+pub struct ForwardAxis(#[cfg_attr(feature = "serde", serde(with = "dir3_serde"))] pub Dir3);
+
+impl Default for ForwardAxis {
+    fn default() -> Self {
+        Self(Dir3::NEG_Z)
+    }
+}
+
+impl ForwardAxis {
+    /// The local rotation turning the axis onto `Vec3::NEG_Z`, applied after the rotation towards
+    /// the target, so that the axis is the one pointing at it
+    pub fn correction(&self) -> Quat {
+        Transform::default()
+            .looking_to(self.0, Dir3::Y)
+            .rotation
+            .inverse()
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A rotation applied after the rotation towards the target, like rolling a sprite 30 degrees,
+/// or turning side facing art 90 degrees
+///
+/// The offset is in the space of the entity looking at its target along `Vec3::NEG_Z`, with
+/// `Vec3::Y` up, so a rotation around Z rolls it. It is part of the rotation being smoothed and
+/// limited, so it stays stable under [`RotateSmoothing`] and changes of parent.
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// let roll = RotateOffset(Quat::from_rotation_z(30f32.to_radians()));
+/// ```
+pub struct RotateOffset(pub Quat);
+
+impl Default for RotateOffset {
+    fn default() -> Self {
+        Self(Quat::IDENTITY)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateWeightState)]
+/// How much of the rotation towards the target is applied, from `0.0`, keeping the current
+/// rotation, to `1.0`, the default, fully facing the target
+///
+/// A weight of `0.3` turns the entity a bit towards the target, a glance, the same part of the
+/// way however often it is rotated. It is weighed from the rotation the entity had before, or
+/// was given since by something else, like an animation setting the rotation of a head bone.
+pub struct RotateWeight(
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider))]
+    pub f32,
+);
+
+impl Default for RotateWeight {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateWeight`] needs to carry between frames
+pub struct RotateWeightState {
+    /// the rotation that is weighed towards the target
+    pub base: Option<Quat>,
+    /// the rotation the entity was given last time
+    pub weighted: Option<Quat>,
+}
+
+impl RotateWeightState {
+    /// Weighs `rotation` from the base rotation, rotated by `current`, which becomes the base
+    /// when it is not the rotation the entity was given last time
+    pub(crate) fn weigh(&mut self, weight: RotateWeight, current: Quat, rotation: Quat) -> Quat {
+        let given = |weighted: Quat| {
+            weighted.abs_diff_eq(current, 1e-6) || weighted.abs_diff_eq(-current, 1e-6)
+        };
+        let base = match self.base {
+            Some(base) if self.weighted.is_some_and(given) => base,
+            _ => current,
+        };
+        self.base = Some(base);
+        base.slerp(rotation, weight.0.clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateAnimationLayerState)]
+/// Blends the rotation towards the target onto the animated rotation of the entity, like a look-at
+/// layer on the head and neck bones of a character, the bones with one are the mask of the layer
+///
+/// The entity turns from where the layer looked last time, not from the animated rotation that
+/// the animation sets every frame, so smoothing and speed limits still apply, and the result is
+/// blended onto the animated rotation by `weight`, also while the entity keeps its rotation. Run
+/// the rotation after the animation with [`RotateTowardsPlugin::after_animation`](crate::RotateTowardsPlugin::after_animation).
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// App::new().add_plugins(RotateTowardsPlugin::default().after_animation());
+///
+/// fn look_at_player(mut commands: Commands, head: Entity, player: Entity) {
+///     commands.entity(head).insert((
+///         RotateTo::new(player),
+///         RotateSmoothing::default(),
+///         RotateAnimationLayer { weight: 0.8 },
+///     ));
+/// }
+/// ```
+pub struct RotateAnimationLayer {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider))]
+    /// how much of the rotation towards the target is blended onto the animated rotation,
+    /// from `0.0` to `1.0`
+    pub weight: f32,
+}
+
+impl Default for RotateAnimationLayer {
+    fn default() -> Self {
+        Self { weight: 1.0 }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateAnimationLayer`] needs to carry between frames
+pub struct RotateAnimationLayerState {
+    /// the local rotation towards the target last time, before it was blended onto the animation
+    pub rotation: Option<Quat>,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How much of the turn towards the target is applied around the up direction, the yaw,
+/// and how much of the rest of it, the pitch, from `0.0` to `1.0`, like a head fully turning
+/// towards the target, but only tilting a bit
+///
+/// Applied before a [`RotateWeight`], which weighs both.
+pub struct RotateAxisWeights {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider))]
+    /// how much of the turn around the up direction is applied
+    pub yaw: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 1.0, display = NumberDisplay::Slider))]
+    /// how much of the turn up or down is applied
+    pub pitch: f32,
+}
+
+impl Default for RotateAxisWeights {
+    fn default() -> Self {
+        Self {
+            yaw: 1.0,
+            pitch: 1.0,
+        }
+    }
+}
+
+impl RotateAxisWeights {
+    /// Weighs the turn from the `current` rotation to `desired`, around the `up` direction, all in
+    /// the same space
+    pub fn weigh(&self, current: Quat, desired: Quat, up: Dir3) -> Quat {
+        let (pitch, yaw) = swing_twist(desired * current.inverse(), up);
+        let yaw = Quat::IDENTITY.slerp(yaw, self.yaw.clamp(0.0, 1.0));
+        let pitch = Quat::IDENTITY.slerp(pitch, self.pitch.clamp(0.0, 1.0));
+        pitch * yaw * current
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A point in the local space of the entity that looks at the target, instead of its origin,
+/// like the eyes of a head whose origin is at the neck
+///
+/// The entity still rotates around its origin, but so that its forward direction through the
+/// pivot points at the target.
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// let eyes = RotatePivot(Vec3::new(0.0, 0.1, -0.1));
+/// ```
+pub struct RotatePivot(pub Vec3);
+
+impl RotatePivot {
+    /// The point the origin of an entity, with the world space `rotation` and `scale`, looks at
+    /// for the forward direction through the pivot to point at `target`
+    pub fn shift(&self, target: Vec3, rotation: Quat, scale: Vec3) -> Vec3 {
+        target - rotation * (scale * self.0)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateBankingState)]
+/// Rolls the entity into turns like an aircraft, around its forward direction, by how fast the
+/// direction towards its target turns, and levels it out again once it stops turning
+pub struct RotateBanking {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// degrees of bank for every degree per second the direction towards the target turns
+    pub degrees_per_turn_rate: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 90.0, display = NumberDisplay::Slider))]
+    /// the largest bank angle, in degrees, either way
+    pub max_degrees: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// how fast the bank angle follows the turning, and recovers when it stops, higher is faster,
+    /// see [`StableInterpolate::smooth_nudge`]
+    pub recovery_rate: f32,
+}
+
+impl Default for RotateBanking {
+    fn default() -> Self {
+        Self {
+            degrees_per_turn_rate: 0.25,
+            max_degrees: 45.0,
+            recovery_rate: 5.0,
+        }
+    }
+}
+
+impl RotateBanking {
+    /// Calculates the roll to apply after the `look` rotation facing the target, in the space
+    /// where the entity looks along `Vec3::NEG_Z`, over `delta_secs`
+    pub fn bank(&self, look: Quat, state: &mut RotateBankingState, delta_secs: f32) -> Quat {
+        if let Some(previous_look) = state.previous_look {
+            if delta_secs > 0.0 {
+                // turning left leaves the previous direction to the right
+                let turn_rate = -AngleToTarget::new(look, previous_look).yaw_degrees / delta_secs;
+                // unlike clamp, does not panic for a negative or NaN largest bank angle
+                let max_degrees = self.max_degrees.abs();
+                let target = (turn_rate * self.degrees_per_turn_rate)
+                    .min(max_degrees)
+                    .max(-max_degrees);
+                state
+                    .bank_degrees
+                    .smooth_nudge(&target, self.recovery_rate, delta_secs);
+            }
+        }
+        state.previous_look = Some(look);
+        Quat::from_rotation_z(state.bank_degrees.to_radians())
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateBanking`] needs to carry between frames
+pub struct RotateBankingState {
+    /// current bank angle, in degrees, positive when banking left
+    pub bank_degrees: f32,
+    /// local rotation facing the target last frame
+    pub previous_look: Option<Quat>,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSaccadesState)]
+/// Adds small and quick random glances away from the target, and a fixation jitter, to the
+/// rotation of the entity, like the eyes of a character in a close-up
+///
+/// The glances are yaws and pitches away from facing the target, applied before the limits.
+/// The same `seed` glances the same way every time, for the same frame times, give every eye
+/// of a pair the same one for them to move together.
+pub struct RotateSaccades {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// how far the glances turn away from the target, at most, in degrees
+    pub amplitude_degrees: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// how many glances there are every second, on average
+    pub frequency: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// how far the entity jitters around where it glances, every frame, in degrees
+    pub jitter_degrees: f32,
+    /// seeds the random glances
+    pub seed: u64,
+}
+
+impl Default for RotateSaccades {
+    fn default() -> Self {
+        Self {
+            amplitude_degrees: 2.0,
+            frequency: 3.0,
+            jitter_degrees: 0.1,
+            seed: 0,
+        }
+    }
+}
+
+impl RotateSaccades {
+    /// Calculates the glance to apply after the rotation facing the target, in the space where
+    /// the entity looks along `Vec3::NEG_Z`, over `delta_secs`
+    pub fn glance(&self, state: &mut RotateSaccadesState, delta_secs: f32) -> Quat {
+        let random = state.random.get_or_insert(self.seed);
+        let mut next = || next_random(random);
+        state.until_next -= delta_secs;
+        if state.until_next <= 0.0 {
+            // within a disk, the glances are evenly spread
+            let angle = next() * TAU;
+            let radius = self.amplitude_degrees * ops::sqrt(next());
+            state.glance_degrees = Vec2::from_angle(angle) * radius;
+            // between half and one and a half of the average time
+            state.until_next = (0.5 + next()) / self.frequency.max(f32::EPSILON);
+        }
+        let jitter = (Vec2::new(next(), next()) * 2.0 - 1.0) * self.jitter_degrees;
+        let degrees = state.glance_degrees + jitter;
+        Quat::from_euler(
+            EulerRot::YXZ,
+            degrees.x.to_radians(),
+            degrees.y.to_radians(),
+            0.0,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateSaccades`] needs to carry between frames
+pub struct RotateSaccadesState {
+    /// the current glance, the yaw and pitch away from the target, in degrees
+    pub glance_degrees: Vec2,
+    /// seconds until the next glance
+    pub until_next: f32,
+    /// the state of the random glances, `None` until it is seeded
+    pub random: Option<u64>,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateIdleWanderState)]
+/// Gently wanders the gaze of the entity around its `rest` local rotation while it has no target,
+/// like when the targeted entity is missing or forgotten behind cover, instead of freezing into
+/// a fixed stare
+///
+/// The gaze follows smooth noise within `max_degrees` of the rest rotation, with the smoothing
+/// and speed limit of the entity. The same `seed` wanders the same way every time, for the same
+/// frame times.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn spawn_guard(mut commands: Commands, intruder: Entity) {
+///     commands.spawn((
+///         RotateTo::new(intruder),
+///         MissingTargetPolicy::Ignore,
+///         RotateSmoothing::default(),
+///         RotateIdleWander {
+///             max_degrees: 20.0,
+///             ..Default::default()
+///         },
+///     ));
+/// }
+/// ```
+pub struct RotateIdleWander {
+    /// the local rotation the gaze wanders around
+    pub rest: Quat,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 180.0, display = NumberDisplay::Slider))]
+    /// how far the gaze wanders from the rest rotation, at most, in degrees
+    pub max_degrees: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// about how many times every second the gaze changes where it wanders to
+    pub frequency: f32,
+    /// seeds the noise the gaze follows
+    pub seed: u64,
+}
+
+impl Default for RotateIdleWander {
+    fn default() -> Self {
+        Self {
+            rest: Quat::IDENTITY,
+            max_degrees: 15.0,
+            frequency: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+impl RotateIdleWander {
+    /// Calculates the local rotation the gaze wanders to, `delta_secs` later
+    pub fn wander(&self, state: &mut RotateIdleWanderState, delta_secs: f32) -> Quat {
+        state.elapsed_secs += delta_secs;
+        let t = state.elapsed_secs * self.frequency;
+        let noise = Vec2::new(
+            value_noise(self.seed, t),
+            value_noise(self.seed ^ 0x5bd1_e995_5bd1_e995, t),
+        );
+        let degrees = noise.clamp_length_max(1.0) * self.max_degrees;
+        self.rest
+            * Quat::from_euler(
+                EulerRot::YXZ,
+                degrees.x.to_radians(),
+                degrees.y.to_radians(),
+                0.0,
+            )
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateIdleWander`] needs to carry between frames
+pub struct RotateIdleWanderState {
+    /// seconds the gaze has wandered for, the noise it follows moves along with them
+    pub elapsed_secs: f32,
+}
+
+/// Smooth noise from `-1.0` to `1.0` at `t`, between random values at the whole numbers
+fn value_noise(seed: u64, t: f32) -> f32 {
+    let whole = ops::floor(t);
+    let at = |whole: f32| {
+        let mut state = seed ^ (whole as i64 as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        next_random(&mut state) * 2.0 - 1.0
+    };
+    let fraction = t - whole;
+    let fraction = fraction * fraction * (3.0 - 2.0 * fraction);
+    at(whole).lerp(at(whole + 1.0), fraction)
+}
+
+/// The next random number from `0.0` up to `1.0`, with a splitmix64 generator
+fn next_random(state: &mut u64) -> f32 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    // the upper 24 bits fill the mantissa
+    (z >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(AxisLockState)]
+/// Only lets the entity turn around a single axis, in the space of its parent, like a ground
+/// turret that only yaws, or a wall mounted camera that only pitches
+///
+/// The direction towards the target is projected onto the plane the entity turns in, the
+/// [`UpDirection`] is not used. While the target is straight along the axis, the rotation is kept.
+///
+/// While it turns gradually, with [`RotateSmoothing`] or a [`RotateSpeedLimit`], towards a target
+/// about straight behind it, it keeps turning the way it did, until the target is clearly on the
+/// other side, instead of turning back and forth while the target crosses behind it.
+pub enum AxisLock {
+    /// only turns around `Vec3::Y`
+    #[default]
+    Yaw,
+    /// only turns around `Vec3::X`
+    Pitch,
+    /// only turns around this axis
+    Axis(#[cfg_attr(feature = "serde", serde(with = "dir3_serde"))] Dir3),
+}
+
+impl AxisLock {
+    /// The axis the entity turns around, in the space of its parent
+    pub fn axis(&self) -> Dir3 {
+        match self {
+            AxisLock::Yaw => Dir3::Y,
+            AxisLock::Pitch => Dir3::X,
+            AxisLock::Axis(axis) => *axis,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The way an entity with an [`AxisLock`] last turned around its axis
+pub struct AxisLockState {
+    /// `1.0` counterclockwise around the axis, `-1.0` clockwise, `0.0` before it turned
+    pub turning: f32,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateConeLimitState)]
+/// Keeps the forward direction of the entity within a cone around its rest rotation, so a head
+/// does not turn to look behind itself, when the target leaves the cone the entity looks
+/// towards the edge of the cone closest to it
+///
+/// The entity still turns freely around its forward direction. [`AngleToTarget`] and
+/// [`AlignmentThreshold`] keep measuring towards the target itself. Not used with [`Rotate2D`].
+pub struct RotateConeLimit {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 180.0, display = NumberDisplay::Slider))]
+    /// the largest angle, in degrees, between the forward direction at rest and the current one
+    pub max_degrees: f32,
+    /// the local rotation of the entity at rest, like the rotation it is spawned with
+    pub rest: Quat,
+    /// whether to trigger [`TargetOutOfRange`](crate::TargetOutOfRange) when the target leaves the cone
+    pub trigger_out_of_range: bool,
+}
+
+impl Default for RotateConeLimit {
+    fn default() -> Self {
+        Self::new(90.0)
+    }
+}
+
+impl RotateConeLimit {
+    /// A cone of `max_degrees` around the identity rotation
+    pub fn new(max_degrees: f32) -> Self {
+        Self {
+            max_degrees,
+            rest: Quat::IDENTITY,
+            trigger_out_of_range: false,
+        }
+    }
+
+    /// Sets the local rotation of the entity at rest
+    pub fn with_rest(mut self, rest: Quat) -> Self {
+        self.rest = rest;
+        self
+    }
+
+    /// Triggers [`TargetOutOfRange`](crate::TargetOutOfRange) when the target leaves the cone
+    pub fn with_out_of_range_trigger(mut self) -> Self {
+        self.trigger_out_of_range = true;
+        self
+    }
+
+    /// Clamps the `look` rotation, pointing `Vec3::NEG_Z` at the target, to the cone, `adjustment`
+    /// is the rotation applied after it to get the rotation of the entity, returns whether the
+    /// target is out of range
+    pub fn clamp(&self, look: Quat, adjustment: Quat) -> (Quat, bool) {
+        let rest = self.rest * adjustment.inverse();
+        let relative = rest.inverse() * look;
+        // the swing turns the forward direction away from rest, the twist turns around it
+        let (swing, twist) = swing_twist(relative, Dir3::NEG_Z);
+        let max_angle = self.max_degrees.to_radians();
+        if swing.angle_between(Quat::IDENTITY) <= max_angle {
+            return (look, false);
+        }
+        let swing = Quat::IDENTITY.rotate_towards(swing, max_angle);
+        (rest * swing * twist, true)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// If the target of an entity with a [`RotateConeLimit`] is currently out of its range
+pub struct RotateConeLimitState {
+    pub out_of_range: bool,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Limits how far the entity can yaw, pitch and roll away from its rest rotation, each on its own,
+/// like the joints of a mechanical rig, after the rotation towards the target is calculated
+///
+/// The angles are the Euler angles, in yaw, pitch, roll order, of the rotation relative to the rest
+/// rotation, where yaw turns left, pitch turns up and roll turns counterclockwise.
+/// Not used with [`Rotate2D`].
+pub struct RotateJointLimits {
+    /// the range the entity can yaw in, around its up direction at rest
+    pub yaw: AngleRange,
+    /// the range the entity can pitch in, around its right direction after yawing
+    pub pitch: AngleRange,
+    /// the range the entity can roll in, around its forward direction
+    pub roll: AngleRange,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// for how many degrees before reaching a limit the rotation eases into it, instead of stopping at it,
+    /// `0` stops hard at the limits
+    pub soft_degrees: f32,
+    /// the local rotation of the entity at rest, like the rotation it is spawned with
+    pub rest: Quat,
+}
+
+impl Default for RotateJointLimits {
+    fn default() -> Self {
+        Self {
+            yaw: AngleRange::FULL,
+            pitch: AngleRange::FULL,
+            roll: AngleRange::FULL,
+            soft_degrees: 0.0,
+            rest: Quat::IDENTITY,
+        }
+    }
+}
+
+impl RotateJointLimits {
+    /// Limits the `look` rotation, pointing `Vec3::NEG_Z` at the target, `adjustment` is the
+    /// rotation applied after it to get the rotation of the entity
+    pub fn limit(&self, look: Quat, adjustment: Quat) -> Quat {
+        let rest = self.rest * adjustment.inverse();
+        let (yaw, pitch, roll) = (rest.inverse() * look).to_euler(EulerRot::YXZ);
+        let limit = |range: AngleRange, angle: f32| {
+            range
+                .soft_clamp(angle.to_degrees(), self.soft_degrees)
+                .to_radians()
+        };
+        rest * Quat::from_euler(
+            EulerRot::YXZ,
+            limit(self.yaw, yaw),
+            limit(self.pitch, pitch),
+            limit(self.roll, roll),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A range of angles, in degrees, see [`RotateJointLimits`]
+pub struct AngleRange {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = -180.0, max = 180.0, display = NumberDisplay::Slider))]
+    pub min_degrees: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = -180.0, max = 180.0, display = NumberDisplay::Slider))]
+    pub max_degrees: f32,
+}
+
+/// Does not limit the angle
+impl Default for AngleRange {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl AngleRange {
+    /// Every angle, does not limit anything
+    pub const FULL: Self = Self::new(-180.0, 180.0);
+
+    /// The angles from `min_degrees` to `max_degrees`, given in either order
+    pub const fn new(min_degrees: f32, max_degrees: f32) -> Self {
+        if min_degrees > max_degrees {
+            return Self::new(max_degrees, min_degrees);
+        }
+        Self {
+            min_degrees,
+            max_degrees,
+        }
+    }
+
+    /// The angles from `-degrees` to `degrees`
+    pub const fn symmetric(degrees: f32) -> Self {
+        Self::new(-degrees, degrees)
+    }
+
+    /// Clamps the angle to the range, easing into the limits over the last `soft_degrees`
+    /// of the range, so the limits are approached without ever being passed
+    pub fn soft_clamp(&self, degrees: f32, soft_degrees: f32) -> f32 {
+        // the fields may have been set in the wrong order
+        let Self {
+            min_degrees,
+            max_degrees,
+        } = Self::new(self.min_degrees, self.max_degrees);
+        let soft_degrees = soft_degrees.min((max_degrees - min_degrees) / 2.0);
+        if soft_degrees <= 0.0 {
+            return degrees.clamp(min_degrees, max_degrees);
+        }
+        // past the start of the soft zone, the remaining degrees decay towards the limit
+        let ease = |over: f32| soft_degrees * (1.0 - ops::exp(-over / soft_degrees));
+        let soft_max = max_degrees - soft_degrees;
+        let soft_min = min_degrees + soft_degrees;
+        if degrees > soft_max {
+            soft_max + ease(degrees - soft_max)
+        } else if degrees < soft_min {
+            soft_min - ease(soft_min - degrees)
+        } else {
+            degrees
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The world space velocity, in units per second, of a targeted entity, used by [`AimLead`]
+/// to aim at where the entity will be, and of a rotator, used by [`UpDirection::Velocity`]
+///
+/// Set it from your movement code, have it copied from a [`VelocitySource`](crate::VelocitySource)
+/// by a [`VelocitySourcePlugin`](crate::VelocitySourcePlugin), or estimated by
+/// [`EstimateVelocity`](crate::EstimateVelocity).
+pub struct TargetVelocity(pub Vec3);
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Aims ahead of a targeted entity with a [`TargetVelocity`], at the position it is predicted
+/// to be at, `position + velocity * lead_time`, so projectiles fired at it hit it while it moves
+///
+/// Works with [`RotateTo`] targeting an entity, and with [`TurretAim`](crate::TurretAim).
+/// [`AngleToTarget`] and [`AlignmentThreshold`] measure towards the predicted position.
+pub enum AimLead {
+    /// aims where the target will be after this many seconds
+    Time(f32),
+    /// aims where a projectile fired at this speed, in units per second, can hit the target,
+    /// when it can never catch up to the target, aims at the target itself
+    ProjectileSpeed(f32),
+}
+
+/// Aims where the target will be in a tenth of a second
+impl Default for AimLead {
+    fn default() -> Self {
+        Self::Time(0.1)
+    }
+}
+
+impl AimLead {
+    /// The predicted position of a target at `position`, when aiming from `origin`
+    pub fn predict(&self, origin: Vec3, position: Vec3, velocity: Vec3) -> Vec3 {
+        let lead_time = match *self {
+            AimLead::Time(lead_time) => lead_time,
+            AimLead::ProjectileSpeed(speed) => {
+                calculate_intercept_time(position - origin, velocity, speed).unwrap_or(0.0)
+            }
+        };
+        position + velocity * lead_time
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSolution)]
+/// Turns the entity with an angular velocity, instead of setting the rotation of its [`Transform`],
+/// for kinematic rigid bodies, whose rotation is moved by the physics engine
+///
+/// The rotation systems keep this at the world space angular velocity, in radians per second,
+/// that turns the entity to its new rotation over the delta of the frame, and leave the
+/// [`Transform`] as it is. It is zero while the entity stops tracking its target, like while its
+/// [`RotateTo`] is disabled or its target is out of view, but not while it waits for its turn of
+/// the [`RotateTimeSlicing`](crate::RotateTimeSlicing). Copy it into the rigid body, or have it
+/// copied by an [`AngularVelocitySinkPlugin`](crate::AngularVelocitySinkPlugin).
+pub struct RotateAngularVelocity(pub Vec3);
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSolution)]
+/// Keeps the new local rotation of the entity here, instead of setting the rotation of its
+/// [`Transform`], for blending it with animations or network smoothed transforms
+///
+/// The rotation systems turn the entity from the rotation kept here, or the one of its
+/// [`Transform`] before the first time, so smoothing and speed limits still apply, and leave the
+/// [`Transform`] as it is. Copy it into a component of your own, or have it copied by a
+/// [`RotationSinkPlugin`](crate::RotationSinkPlugin).
+pub struct RotateLocalRotation(pub Option<Quat>);
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateSolution)]
+/// Publishes the new rotation of the entity here, instead of setting the rotation of its
+/// [`Transform`], for systems of your own to consume, when another controller owns the transform
+///
+/// The rotation systems turn the entity from the rotation of its [`Transform`], and keep this up
+/// to date every time they rotate it. Every rotator gets one with
+/// [`RotateTowardsPlugin::compute_only`](crate::RotateTowardsPlugin::compute_only).
+pub struct DesiredLookRotation {
+    /// the new rotation, relative to the parent
+    pub local: Quat,
+    /// the new rotation, in world space
+    pub world: Quat,
+}
+
+impl Default for DesiredLookRotation {
+    fn default() -> Self {
+        Self {
+            local: Quat::IDENTITY,
+            world: Quat::IDENTITY,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTorqueState, RotateSolution)]
+/// Turns the entity with a torque, instead of setting the rotation of its [`Transform`],
+/// for dynamic rigid bodies, like physical turrets or the thrusters of a ship
+///
+/// A proportional-derivative controller calculates the torque, from how far the entity is turned
+/// away from its new rotation, and how fast it turns, which is measured from how its rotation
+/// changes between frames. The rotation systems keep the world space torque in
+/// [`RotateTorqueState::torque`], and leave the [`Transform`] as it is. The torque is zero while
+/// the entity stops tracking its target, like [`RotateAngularVelocity`]. Apply it to the rigid
+/// body, or have it applied by a [`TorqueSinkPlugin`](crate::TorqueSinkPlugin).
+pub struct RotateTorque {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// torque for every radian the entity is turned away from its new rotation
+    pub stiffness: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// torque against every radian per second the entity turns
+    pub damping: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// the largest torque
+    pub max_torque: f32,
+}
+
+impl Default for RotateTorque {
+    fn default() -> Self {
+        Self {
+            stiffness: 10.0,
+            damping: 2.0,
+            max_torque: f32::INFINITY,
+        }
+    }
+}
+
+impl RotateTorque {
+    /// Calculates the world space torque turning the entity, currently rotated by `rotation`
+    /// in world space, towards `desired`, over `delta_secs`
+    pub fn torque(
+        &self,
+        rotation: Quat,
+        desired: Quat,
+        state: &mut RotateTorqueState,
+        delta_secs: f32,
+    ) -> Vec3 {
+        let error = calculate_angular_velocity(rotation, desired, 1.0);
+        let angular_velocity = state.previous_rotation.map_or(Vec3::ZERO, |previous| {
+            calculate_angular_velocity(previous, rotation, delta_secs)
+        });
+        state.previous_rotation = Some(rotation);
+        (error * self.stiffness - angular_velocity * self.damping).clamp_length_max(self.max_torque)
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateTorque`] needs to carry between frames
+pub struct RotateTorqueState {
+    /// the world space torque to apply this frame
+    pub torque: Vec3,
+    /// world space rotation of the entity last frame
+    pub previous_rotation: Option<Quat>,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(FieldOfViewState)]
+/// Only turns the entity towards its target while the target is within a cone of vision, like a
+/// guard that only notices what is in front of it, keeps the rotation otherwise
+///
+/// Triggers [`TargetEnteredView`](crate::TargetEnteredView) and
+/// [`TargetLeftView`](crate::TargetLeftView) when the target enters and leaves the cone.
+pub struct FieldOfView {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 360.0, display = NumberDisplay::Slider))]
+    /// the full angle of the cone, in degrees, like the field of view of a camera
+    pub degrees: f32,
+    /// what the cone faces along
+    pub reference: FieldOfViewReference,
+}
+
+impl Default for FieldOfView {
+    fn default() -> Self {
+        Self {
+            degrees: 90.0,
+            reference: FieldOfViewReference::default(),
+        }
+    }
+}
+
+impl FieldOfView {
+    /// If the world space `direction` is within the cone, facing along `forward`
+    pub fn contains(&self, direction: Vec3, forward: Vec3) -> bool {
+        forward.angle_between(direction).to_degrees() <= self.degrees / 2.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What the cone of a [`FieldOfView`] faces along, the forward direction of the entity,
+/// see [`ForwardAxis`], with one of these rotations
+pub enum FieldOfViewReference {
+    /// the current rotation of the entity, so the cone follows the target once it is tracked
+    #[default]
+    Current,
+    /// the rotation of the parent, like the body a head is attached to
+    Parent,
+    /// this local rotation, in the space of the parent, like the rotation the entity is spawned with
+    Rest(Quat),
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// If the target of an entity with a [`FieldOfView`] is currently in view
+pub struct FieldOfViewState {
+    pub in_view: bool,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(EngagementRangeState)]
+/// Only turns the entity towards its target while the target is between a min and a max distance
+/// from it, like the engagement envelope of a turret
+///
+/// Triggers [`TargetEnteredRange`](crate::TargetEnteredRange) and
+/// [`TargetLeftRange`](crate::TargetLeftRange) when the target enters and leaves the range.
+/// Targets that are directions are always in range.
+pub struct EngagementRange {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// the distance closer than which the target is ignored
+    pub min_distance: f32,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// the distance farther than which the target is ignored
+    pub max_distance: f32,
+    /// what the entity does while the target is out of range
+    pub outside: OutsideRange,
+}
+
+impl Default for EngagementRange {
+    fn default() -> Self {
+        Self::new(0.0, f32::INFINITY)
+    }
+}
+
+impl EngagementRange {
+    /// Tracks targets from `min_distance` to `max_distance` away, keeps the rotation otherwise
+    pub fn new(min_distance: f32, max_distance: f32) -> Self {
+        Self {
+            min_distance,
+            max_distance,
+            outside: OutsideRange::Freeze,
+        }
+    }
+
+    /// Turns back to the `rest` local rotation while the target is out of range
+    pub fn with_return_to_rest(mut self, rest: Quat) -> Self {
+        self.outside = OutsideRange::ReturnToRest(rest);
+        self
+    }
+
+    /// If a target at `distance` is in range
+    pub fn contains(&self, distance: f32) -> bool {
+        (self.min_distance..=self.max_distance).contains(&distance)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What an entity with an [`EngagementRange`] does while its target is out of range
+pub enum OutsideRange {
+    /// keeps its rotation
+    #[default]
+    Freeze,
+    /// turns back to this local rotation, with its smoothing and speed limit
+    ReturnToRest(Quat),
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What an entity does while the point it targets is at its own position, within its
+/// [`RotateEpsilon`], where there is no direction to look along
+///
+/// Without this component the entity keeps its rotation, the same as with
+/// [`CoLocatedTarget::Freeze`], rather than flipping to an arbitrary one.
+pub enum CoLocatedTarget {
+    /// keeps its rotation
+    #[default]
+    Freeze,
+    /// turns back to this local rotation, with its smoothing and speed limit
+    ReturnToRest(Quat),
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateReturnToRestState)]
+/// Turns the entity back to its rest local rotation once it stops rotating towards a target,
+/// when its [`RotateTo`] is removed or disabled, rather than leaving it frozen mid-glance
+///
+/// Without a `rest` rotation, the entity rests at the rotation it had when the rotation systems
+/// first saw this component, insert it along with the [`RotateTo`] for that to be the rotation
+/// it was spawned with.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn spawn_statue_head(mut commands: Commands, player: Entity) {
+///     commands.spawn((RotateTo::new(player), RotateReturnToRest::default()));
+/// }
+///
+/// fn stop_watching(mut commands: Commands, head: Entity) {
+///     commands.entity(head).remove::<RotateTo>();
+/// }
+/// ```
+pub struct RotateReturnToRest {
+    /// the local rotation to turn back to, the one it had at first without one
+    pub rest: Option<Quat>,
+    /// shape of the turn back, sampled from 0 to 1 over the duration
+    pub ease: EaseFunction,
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0))]
+    /// how many seconds the turn back takes
+    pub duration: f32,
+}
+
+impl Default for RotateReturnToRest {
+    fn default() -> Self {
+        Self {
+            rest: None,
+            ease: EaseFunction::SmoothStep,
+            duration: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`RotateReturnToRest`] needs to carry between frames
+pub struct RotateReturnToRestState {
+    /// the local rotation it turns back to, once it is known
+    pub rest: Option<Quat>,
+    /// local rotation the current turn back started from, while it is not rotating towards a target
+    pub transition_start: Option<Quat>,
+    /// seconds since the current turn back started
+    pub transition_elapsed: f32,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy-inspector-egui",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How an entity keeps a stable orientation while it looks within `degrees` of straight along its
+/// up direction, or against it, where the up direction no longer tells how it should be rolled
+///
+/// Without this component the entity keeps its roll within a degree of the poles, the same as
+/// with the default.
+pub struct PolePolicy {
+    #[cfg_attr(feature = "bevy-inspector-egui", inspector(min = 0.0, max = 90.0, display = NumberDisplay::Slider))]
+    /// how close to the poles the look direction is, in degrees, for the `fallback` to be used
+    pub degrees: f32,
+    /// what the entity does near the poles
+    pub fallback: PoleFallback,
+}
+
+impl Default for PolePolicy {
+    fn default() -> Self {
+        Self {
+            degrees: 1.0,
+            fallback: PoleFallback::KeepRoll,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What an entity with a [`PolePolicy`] does while it looks near the poles of its up direction
+pub enum PoleFallback {
+    /// keeps the up direction it currently has, so it does not spin around the look direction
+    #[default]
+    KeepRoll,
+    /// uses this world space up direction instead, like the forward direction of a vehicle
+    Up(#[cfg_attr(feature = "serde", serde(with = "dir3_serde"))] Dir3),
+    /// looks no closer to the poles than the `degrees` of the [`PolePolicy`]
+    Clamp,
+}
+
+impl PolePolicy {
+    /// The world space look `direction` and up direction to rotate with, changed by the fallback
+    /// while `direction` is near the poles of `updir`, for a rotator currently at `rotator_gt`
+    pub fn stabilize(
+        &self,
+        direction: Vec3,
+        updir: Dir3,
+        rotator_gt: &GlobalTransform,
+    ) -> (Vec3, Dir3) {
+        let Ok(look) = Dir3::new(direction) else {
+            return (direction, updir);
+        };
+        let cos = look.dot(*updir);
+        if cos.abs() < ops::cos(self.degrees.to_radians()) {
+            return (direction, updir);
+        }
+        // the pole the entity looks towards
+        let pole = if cos > 0.0 { updir } else { -updir };
+        match self.fallback {
+            PoleFallback::KeepRoll => {
+                // while the current up is along the pole too, the entity just turned towards it,
+                // from facing forward, so its back or its forward is where its up turns to
+                let current_up = rotator_gt.up();
+                let up = if current_up.dot(*look).abs() < ops::cos(self.degrees.to_radians()) {
+                    current_up
+                } else if current_up.dot(*pole) > 0.0 {
+                    rotator_gt.back()
+                } else {
+                    rotator_gt.forward()
+                };
+                (direction, up)
+            }
+            PoleFallback::Up(up) => (direction, up),
+            PoleFallback::Clamp => {
+                // straight along the pole, keep facing the way the entity currently faces
+                let horizontal = direction
+                    .reject_from_normalized(*pole)
+                    .try_normalize()
+                    .or_else(|| {
+                        rotator_gt
+                            .forward()
+                            .reject_from_normalized(*pole)
+                            .try_normalize()
+                    })
+                    .unwrap_or_else(|| pole.any_orthonormal_vector());
+                let angle = self.degrees.to_radians();
+                (
+                    ops::cos(angle) * *pole + ops::sin(angle) * horizontal,
+                    updir,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// If the target of an entity with an [`EngagementRange`] is currently in range
+pub struct EngagementRangeState {
+    pub in_range: bool,
+}
+
+/// Serializes [`Dir3`] as its [`Vec3`], and validates it is a direction when deserializing,
+/// so the representation does not depend on how `Dir3` itself is serialized
+#[cfg(feature = "serde")]
+pub(crate) mod dir3_serde {
+    use bevy_math::prelude::*;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(dir: &Dir3, serializer: S) -> Result<S::Ok, S::Error> {
+        dir.as_vec3().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dir3, D::Error> {
+        let vec = Vec3::deserialize(deserializer)?;
+        Dir3::new(vec).map_err(|err| D::Error::custom(format_args!("invalid Dir3 {vec}: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angle_range_in_either_order() {
+        let range = AngleRange::new(60.0, -10.0);
+        assert_eq!(range, AngleRange::new(-10.0, 60.0));
+        assert_eq!(range.soft_clamp(90.0, 0.0), 60.0);
+        assert_eq!(range.soft_clamp(-30.0, 0.0), -10.0);
+
+        // set directly, the fields are not ordered
+        let range = AngleRange {
+            min_degrees: 60.0,
+            max_degrees: -10.0,
+        };
+        assert_eq!(range.soft_clamp(90.0, 0.0), 60.0);
+        let eased = range.soft_clamp(90.0, 10.0);
+        assert!(eased > 50.0 && eased < 60.0, "{eased}");
+        assert_eq!(range.soft_clamp(20.0, 10.0), 20.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rotate_to_saved_without_a_target_offset() {
+        let rotate_to: RotateTo =
+            ron::from_str("(target: Point((1.0, 2.0, 3.0)), updir: Parent, enabled: false)")
+                .unwrap();
+        assert_eq!(
+            rotate_to.target,
+            RotateTarget::Point(Vec3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(rotate_to.updir, UpDirection::Parent);
+        assert_eq!(rotate_to.target_offset, Vec3::ZERO);
+        assert!(!rotate_to.enabled);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rotate_to_saved_without_enabled() {
+        let rotate_to: RotateTo =
+            ron::from_str("(target: Point((1.0, 2.0, 3.0)), updir: Parent)").unwrap();
+        assert_eq!(rotate_to.target_offset, Vec3::ZERO);
+        assert!(rotate_to.enabled);
+    }
+
+    /// The yaw of a rotation around `Vec3::Y`, in radians
+    fn yaw(rotation: Quat) -> f32 {
+        2.0 * ops::atan2(rotation.y, rotation.w)
+    }
+
+    #[test]
+    fn exponential_smoothing_is_frame_rate_independent() {
+        let smoothing = RotateSmoothing::Exponential { decay_rate: 5.0 };
+        let desired = Quat::from_rotation_y(1.0);
+        let mut state = RotateSmoothingState::default();
+        let smooth = |steps: u32, state: &mut RotateSmoothingState| {
+            let delta_secs = 0.5 / steps as f32;
+            (0..steps).fold(Quat::IDENTITY, |rotation, _| {
+                smoothing.smooth(rotation, desired, state, delta_secs, false)
+            })
+        };
+        let once = smooth(1, &mut state);
+        let often = smooth(30, &mut state);
+        assert!((yaw(once) - yaw(often)).abs() < 1e-4);
+        // the remaining angle decays by e^(-5 * 0.5)
+        assert!((1.0 - yaw(once) - ops::exp(-2.5)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn spring_settles_and_overshoots_when_underdamped() {
+        let desired = Quat::from_rotation_y(1.0);
+        let yaws = |smoothing: RotateSmoothing| {
+            let mut state = RotateSmoothingState::default();
+            let mut rotation = Quat::IDENTITY;
+            (0..300)
+                .map(|_| {
+                    rotation = smoothing.smooth(rotation, desired, &mut state, 1.0 / 60.0, false);
+                    yaw(rotation)
+                })
+                .collect::<Vec<_>>()
+        };
+        let critical = yaws(RotateSmoothing::critically_damped(50.0));
+        assert!(critical.iter().all(|yaw| *yaw <= 1.0 + 1e-4));
+        assert!((critical[299] - 1.0).abs() < 1e-3);
+        let under = yaws(RotateSmoothing::Spring {
+            stiffness: 50.0,
+            damping: 2.0,
+        });
+        assert!(under.iter().any(|yaw| *yaw > 1.1));
+        assert!((under[299] - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn eased_transition_restarts_once_retargeted() {
+        let smoothing = RotateSmoothing::Eased {
+            ease: EaseFunction::Linear,
+            duration: 1.0,
+        };
+        let mut state = RotateSmoothingState::default();
+        let desired = Quat::from_rotation_y(1.0);
+        let mut rotation = Quat::IDENTITY;
+        for frame in 0..5 {
+            rotation = smoothing.smooth(rotation, desired, &mut state, 0.1, frame == 0);
+        }
+        assert!((yaw(rotation) - 0.5).abs() < 1e-4);
+        for _ in 0..6 {
+            rotation = smoothing.smooth(rotation, desired, &mut state, 0.1, false);
+        }
+        assert!((yaw(rotation) - 1.0).abs() < 1e-4);
+
+        // a new transition starts from where it is
+        let desired = Quat::from_rotation_y(-1.0);
+        rotation = smoothing.smooth(rotation, desired, &mut state, 0.5, true);
+        assert!(yaw(rotation).abs() < 1e-4);
+    }
+
+    #[test]
+    fn pole_policy_stabilizes_only_near_the_poles() {
+        let gt = GlobalTransform::IDENTITY;
+        let policy = |fallback| PolePolicy {
+            degrees: 10.0,
+            fallback,
+        };
+
+        // straight up, the entity keeps an up that is not along the look direction
+        let (direction, up) = policy(PoleFallback::KeepRoll).stabilize(Vec3::Y, Dir3::Y, &gt);
+        assert_eq!(direction, Vec3::Y);
+        assert!(up.dot(direction).abs() < 1e-5);
+
+        let (direction, up) =
+            policy(PoleFallback::Up(Dir3::X)).stabilize(Vec3::NEG_Y, Dir3::Y, &gt);
+        assert_eq!((direction, up), (Vec3::NEG_Y, Dir3::X));
+
+        // clamped, it looks 10 degrees off the pole, towards where it faces
+        let (direction, up) = policy(PoleFallback::Clamp).stabilize(Vec3::Y, Dir3::Y, &gt);
+        assert_eq!(up, Dir3::Y);
+        assert!((direction.angle_between(Vec3::Y).to_degrees() - 10.0).abs() < 1e-3);
+        assert!(direction.z < 0.0);
+
+        // away from the poles, nothing changes
+        let away = Vec3::new(0.0, 1.0, -1.0);
+        for fallback in [
+            PoleFallback::KeepRoll,
+            PoleFallback::Up(Dir3::X),
+            PoleFallback::Clamp,
+        ] {
+            assert_eq!(
+                policy(fallback).stabilize(away, Dir3::Y, &gt),
+                (away, Dir3::Y)
+            );
+        }
+    }
+
+    #[test]
+    fn banking_limited_by_any_largest_angle() {
+        let bank = |max_degrees: f32| {
+            let banking = RotateBanking {
+                degrees_per_turn_rate: 1.0,
+                max_degrees,
+                // straight to the bank angle
+                recovery_rate: f32::INFINITY,
+            };
+            let mut state = RotateBankingState::default();
+            banking.bank(Quat::IDENTITY, &mut state, 0.1);
+            // turning left at 100 degrees per second
+            banking.bank(Quat::from_rotation_y(10f32.to_radians()), &mut state, 0.1);
+            state.bank_degrees
+        };
+        assert!((bank(30.0) - 30.0).abs() < 1e-3);
+        assert!((bank(-30.0) - 30.0).abs() < 1e-3);
+        assert!((bank(f32::NAN) - 100.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,235 @@
+use bevy_app::prelude::*;
+use bevy_color::{palettes::css, Color};
+use bevy_ecs::{prelude::*, query::QueryData};
+use bevy_gizmos::prelude::*;
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    components::{ForwardAxis, RotateConeLimit, RotateTarget},
+    systems::RotateTargets,
+};
+
+/// color of the line from a rotator to its target
+const TARGET_COLOR: Color = Color::Srgba(css::YELLOW);
+/// color of the forward direction of a rotator
+const FORWARD_COLOR: Color = Color::Srgba(css::DODGER_BLUE);
+/// color of the up direction of a rotator
+const UP_COLOR: Color = Color::Srgba(css::LIME);
+/// color of the cone of a [`RotateConeLimit`]
+const CONE_COLOR: Color = Color::Srgba(css::ORANGE);
+
+/// Plugin that draws a line from every rotator to its target, and its forward and up directions,
+/// with the [`Gizmos`] of Bevy, to see wrong targets and up directions at a glance
+///
+/// The forward direction is the one of its [`ForwardAxis`], a direction to look along is drawn
+/// as a line from the rotator along it. Optionally the cones of [`RotateConeLimit`]s are drawn too,
+/// see [`RotateTowardsDebugPlugin::with_cones`]. Needs the `GizmoPlugin` of Bevy.
+///
+/// What is drawn, and how, is configured at runtime with the [`RotateTowardsGizmos`] config group,
+/// and paused with the [`RotateTowardsDebugEnabled`] resource.
+#[derive(Default)]
+pub struct RotateTowardsDebugPlugin {
+    /// the config group the drawing starts with
+    config: RotateTowardsGizmos,
+}
+
+impl RotateTowardsDebugPlugin {
+    /// Sets how long the lines of the forward and up directions are, and of the directions
+    /// rotators look along
+    pub fn with_axes_length(mut self, axes_length: f32) -> Self {
+        self.config.axes_length = axes_length;
+        self
+    }
+
+    /// Also draws the cones of [`RotateConeLimit`]s, around the forward direction at rest
+    pub fn with_cones(mut self) -> Self {
+        self.config.cones = true;
+        self
+    }
+
+    /// Only draws the rotators with a [`ShowRotateGizmos`]
+    pub fn with_selected_only(mut self) -> Self {
+        self.config.draw_all = false;
+        self
+    }
+}
+
+impl Plugin for RotateTowardsDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_gizmo_config(self.config.clone(), GizmoConfig::default())
+            .register_type::<RotateTowardsGizmos>()
+            .register_type::<ShowRotateGizmos>()
+            .register_type::<RotateTowardsDebugEnabled>()
+            .init_resource::<RotateTowardsDebugEnabled>()
+            .add_systems(
+                PostUpdate,
+                draw_rotators
+                    .after(TransformSystems::Propagate)
+                    .run_if(rotate_towards_debug_enabled),
+            );
+    }
+}
+
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+#[reflect(Clone, Default)]
+/// The [`GizmoConfigGroup`] the [`RotateTowardsDebugPlugin`] draws with, change it, and its
+/// [`GizmoConfig`], with the `GizmoConfigStore`
+pub struct RotateTowardsGizmos {
+    /// how long the lines of the forward and up directions are, and of the directions rotators
+    /// look along
+    pub axes_length: f32,
+    /// whether the cones of [`RotateConeLimit`]s are drawn
+    pub cones: bool,
+    /// whether every rotator is drawn, or only the ones with a [`ShowRotateGizmos`]
+    pub draw_all: bool,
+}
+
+impl Default for RotateTowardsGizmos {
+    fn default() -> Self {
+        Self {
+            axes_length: 1.0,
+            cones: false,
+            draw_all: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+/// Draws the rotator while [`RotateTowardsGizmos::draw_all`] is `false`, to only see the selected
+/// rotators
+pub struct ShowRotateGizmos;
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+/// Pauses the drawing of the [`RotateTowardsDebugPlugin`] while `false`, like toggled with a
+/// debug key
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn toggle_debug_drawing(mut enabled: ResMut<RotateTowardsDebugEnabled>) {
+///     enabled.0 = !enabled.0;
+/// }
+/// ```
+pub struct RotateTowardsDebugEnabled(pub bool);
+
+impl Default for RotateTowardsDebugEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Run condition that is true unless the [`RotateTowardsDebugEnabled`] resource pauses the drawing
+fn rotate_towards_debug_enabled(enabled: Option<Res<RotateTowardsDebugEnabled>>) -> bool {
+    enabled.is_none_or(|enabled| enabled.0)
+}
+
+/// What is drawn of a rotator
+#[derive(QueryData)]
+struct DebugRotator {
+    global_transform: &'static GlobalTransform,
+    child_of: Option<&'static ChildOf>,
+    targets: RotateTargets,
+    forward_axis: Option<&'static ForwardAxis>,
+    cone_limit: Option<&'static RotateConeLimit>,
+    shown: Has<ShowRotateGizmos>,
+}
+
+/// Draws the targets, directions and cones of every rotator
+fn draw_rotators(
+    mut gizmos: Gizmos<RotateTowardsGizmos>,
+    rotators: Query<DebugRotator>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    let RotateTowardsGizmos {
+        axes_length: length,
+        cones,
+        draw_all,
+    } = *gizmos.config_ext;
+    for DebugRotatorItem {
+        global_transform: rotator_gt,
+        child_of,
+        targets,
+        forward_axis,
+        cone_limit,
+        shown,
+    } in rotators.iter()
+    {
+        if !draw_all && !shown {
+            continue;
+        }
+        let position = rotator_gt.translation();
+        let translation = |entity| {
+            global_transforms
+                .get(entity)
+                .ok()
+                .map(|gt| gt.translation())
+        };
+        match targets {
+            (Some(rotate_to), ..) => match rotate_to.target {
+                RotateTarget::Entity(entity) => {
+                    if let Some(target) = translation(entity) {
+                        gizmos.line(position, target, TARGET_COLOR);
+                    }
+                }
+                RotateTarget::Point(point) => gizmos.line(position, point, TARGET_COLOR),
+                RotateTarget::Direction(direction) => {
+                    gizmos.ray(position, direction * length, TARGET_COLOR);
+                }
+            },
+            (None, Some(rotate_to_direction), _) => {
+                gizmos.ray(
+                    position,
+                    rotate_to_direction.direction * length,
+                    TARGET_COLOR,
+                );
+            }
+            (None, None, Some(rotate_to_centroid)) => {
+                if let Some(centroid) = rotate_to_centroid.centroid(translation) {
+                    gizmos.line(position, centroid, TARGET_COLOR);
+                }
+            }
+            (None, None, None) => {}
+        }
+
+        // the axes of the model, which is rotated by the correction of its forward axis
+        let correction = forward_axis.map_or(Quat::IDENTITY, ForwardAxis::correction);
+        let rotation = rotator_gt.rotation() * correction.inverse();
+        gizmos.ray(position, rotation * Vec3::NEG_Z * length, FORWARD_COLOR);
+        gizmos.ray(position, rotation * Vec3::Y * length, UP_COLOR);
+
+        if let (true, Some(cone_limit)) = (cones, cone_limit) {
+            let parent_rotation = child_of
+                .and_then(|child_of| global_transforms.get(child_of.parent()).ok())
+                .map_or(Quat::IDENTITY, GlobalTransform::rotation);
+            let rest = parent_rotation * cone_limit.rest * correction.inverse();
+            draw_cone(
+                &mut gizmos,
+                position,
+                rest * Vec3::NEG_Z,
+                cone_limit.max_degrees,
+                length,
+            );
+        }
+    }
+}
+
+/// Draws a cone from `apex` around `axis`, its sides `length` long and `degrees` away from it
+fn draw_cone(
+    gizmos: &mut Gizmos<RotateTowardsGizmos>,
+    apex: Vec3,
+    axis: Vec3,
+    degrees: f32,
+    length: f32,
+) {
+    let (sin, cos) = ops::sin_cos(degrees.clamp(0.0, 180.0).to_radians());
+    let center = apex + axis * cos * length;
+    let rotation = Quat::from_rotation_arc(Vec3::Z, axis);
+    gizmos.circle(Isometry3d::new(center, rotation), sin * length, CONE_COLOR);
+    for side in [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y] {
+        gizmos.line(apex, center + rotation * side * sin * length, CONE_COLOR);
+    }
+}
@@ -0,0 +1,98 @@
+use core::sync::atomic::Ordering;
+
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
+use bevy_platform::time::Instant;
+
+use crate::{
+    plugin::RotateTowardsSystems,
+    systems::{RotatedRotators, WithRotateTargets},
+};
+
+/// Plugin that measures how many rotators there are, how many of them are rotated and how long
+/// rotating them takes, as diagnostics shown by the diagnostic overlays and
+/// `LogDiagnosticsPlugin` of Bevy
+///
+/// By default it measures in [`PostUpdate`], measure in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with
+/// [`RotateTowardsDiagnosticsPlugin::in_schedule`].
+pub struct RotateTowardsDiagnosticsPlugin {
+    /// the schedule the rotators are rotated in
+    schedule: InternedScheduleLabel,
+}
+
+impl Default for RotateTowardsDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+        }
+    }
+}
+
+impl RotateTowardsDiagnosticsPlugin {
+    /// Number of entities with a [`RotateTo`](crate::RotateTo),
+    /// [`RotateToDirection`](crate::RotateToDirection) or
+    /// [`RotateToCentroid`](crate::RotateToCentroid)
+    pub const ROTATORS: DiagnosticPath = DiagnosticPath::const_new("rotate_towards/rotators");
+
+    /// Number of rotators whose rotation was set this frame
+    pub const ROTATED: DiagnosticPath = DiagnosticPath::const_new("rotate_towards/rotated");
+
+    /// Milliseconds spent in the [`RotateTowardsSystems::Rotate`] set this frame
+    pub const ROTATE_TIME: DiagnosticPath = DiagnosticPath::const_new("rotate_towards/rotate_time");
+
+    /// Measures in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl Plugin for RotateTowardsDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ROTATORS))
+            .register_diagnostic(Diagnostic::new(Self::ROTATED))
+            .register_diagnostic(Diagnostic::new(Self::ROTATE_TIME).with_suffix("ms"))
+            .init_resource::<RotatedRotators>()
+            .init_resource::<RotateStarted>()
+            .add_systems(
+                self.schedule,
+                (
+                    start_rotate_diagnostics
+                        .after(RotateTowardsSystems::Prepare)
+                        .before(RotateTowardsSystems::Rotate),
+                    rotate_diagnostics.after(RotateTowardsSystems::Rotate),
+                ),
+            );
+    }
+}
+
+/// When the rotation systems started this frame
+#[derive(Resource, Default)]
+struct RotateStarted(Option<Instant>);
+
+fn start_rotate_diagnostics(mut started: ResMut<RotateStarted>) {
+    started.0 = Some(Instant::now());
+}
+
+fn rotate_diagnostics(
+    mut diagnostics: Diagnostics,
+    mut started: ResMut<RotateStarted>,
+    rotated: Res<RotatedRotators>,
+    rotators: Query<(), WithRotateTargets>,
+) {
+    if let Some(started) = started.0.take() {
+        diagnostics.add_measurement(&RotateTowardsDiagnosticsPlugin::ROTATE_TIME, || {
+            started.elapsed().as_secs_f64() * 1000.0
+        });
+    }
+    let rotated = rotated.0.swap(0, Ordering::Relaxed);
+    diagnostics.add_measurement(&RotateTowardsDiagnosticsPlugin::ROTATED, || rotated as f64);
+    diagnostics.add_measurement(&RotateTowardsDiagnosticsPlugin::ROTATORS, || {
+        rotators.count() as f64
+    });
+}
@@ -0,0 +1,83 @@
+use bevy_ecs::prelude::*;
+
+/// Triggered on a rotator when its target is missing and its [`MissingTargetPolicy`](crate::MissingTargetPolicy) is
+/// [`MissingTargetPolicy::EmitEvent`](crate::MissingTargetPolicy::EmitEvent)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RotateTargetMissing {
+    /// the rotator
+    pub entity: Entity,
+    /// the target that could not be found
+    pub target: Entity,
+}
+
+/// Triggered on a rotator when the entity it targets is found missing, after having been found,
+/// or the first time it is looked for
+///
+/// This is triggered regardless of the [`MissingTargetPolicy`](crate::MissingTargetPolicy) of the rotator
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetLost {
+    /// the rotator
+    pub entity: Entity,
+    /// the target that could not be found
+    pub target: Entity,
+}
+
+/// Triggered on a rotator when the entity it targets can be found again, after a [`TargetLost`]
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetReacquired {
+    /// the rotator
+    pub entity: Entity,
+    /// the target that was found
+    pub target: Entity,
+}
+
+/// Triggered on a rotator when it becomes aligned with its target, see [`AlignmentThreshold`](crate::AlignmentThreshold)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aligned {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when it is no longer aligned with its target, if
+/// [`AlignmentThreshold::trigger_lost`](crate::AlignmentThreshold::trigger_lost) is set
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlignmentLost {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when its target enters its [`EngagementRange`](crate::EngagementRange)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetEnteredRange {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when its target leaves its [`EngagementRange`](crate::EngagementRange)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetLeftRange {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when its target enters its [`FieldOfView`](crate::FieldOfView)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetEnteredView {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when its target leaves its [`FieldOfView`](crate::FieldOfView)
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetLeftView {
+    /// the rotator
+    pub entity: Entity,
+}
+
+/// Triggered on a rotator when its target leaves the cone it can turn in, if
+/// [`RotateConeLimit::trigger_out_of_range`](crate::RotateConeLimit::trigger_out_of_range) is set
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TargetOutOfRange {
+    /// the rotator
+    pub entity: Entity,
+}
@@ -0,0 +1,142 @@
+use core::any::{Any, TypeId};
+
+use bevy_ecs::prelude::*;
+use bevy_inspector_egui::{
+    egui, inspector_egui_impls::InspectorPrimitive, reflect_inspector::InspectorUi,
+};
+use bevy_math::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::components::RotateTarget;
+
+/// Picks the kind of target, then the entity out of the entities with a [`GlobalTransform`], by
+/// their [`Name`], or edits the point or direction, registered by the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with the `bevy-inspector-egui` feature
+impl InspectorPrimitive for RotateTarget {
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        _: &dyn Any,
+        id: egui::Id,
+        mut env: InspectorUi<'_, '_>,
+    ) -> bool {
+        let mut changed = false;
+        egui::ComboBox::from_id_salt(id.with("kind"))
+            .selected_text(kind_name(self))
+            .show_ui(ui, |ui| {
+                for kind in [
+                    RotateTarget::Entity(Entity::PLACEHOLDER),
+                    RotateTarget::Point(Vec3::ZERO),
+                    RotateTarget::Direction(Dir3::NEG_Z),
+                ] {
+                    let selected = kind_name(self) == kind_name(&kind);
+                    if ui.selectable_label(selected, kind_name(&kind)).clicked() && !selected {
+                        *self = kind;
+                        changed = true;
+                    }
+                }
+            });
+        changed |= match self {
+            RotateTarget::Entity(entity) => {
+                let mut picked = *entity;
+                egui::ComboBox::from_id_salt(id.with("entity"))
+                    .selected_text(entity_name(&mut env, picked))
+                    .show_ui(ui, |ui| {
+                        for candidate in entities_with_global_transforms(&env) {
+                            let name = entity_name(&mut env, candidate);
+                            ui.selectable_value(&mut picked, candidate, name);
+                        }
+                    });
+                let picked_another = picked != *entity;
+                *entity = picked;
+                picked_another
+            }
+            RotateTarget::Point(point) => env.ui_for_reflect(point, ui),
+            RotateTarget::Direction(direction) => env.ui_for_reflect(direction, ui),
+        };
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        ui: &mut egui::Ui,
+        _: &dyn Any,
+        _: egui::Id,
+        mut env: InspectorUi<'_, '_>,
+    ) {
+        match self {
+            RotateTarget::Entity(entity) => {
+                ui.label(entity_name(&mut env, *entity));
+            }
+            RotateTarget::Point(point) => env.ui_for_reflect_readonly(point, ui),
+            RotateTarget::Direction(direction) => env.ui_for_reflect_readonly(direction, ui),
+        }
+    }
+}
+
+/// The name of the kind of target
+fn kind_name(target: &RotateTarget) -> &'static str {
+    match target {
+        RotateTarget::Entity(_) => "Entity",
+        RotateTarget::Point(_) => "Point",
+        RotateTarget::Direction(_) => "Direction",
+    }
+}
+
+/// The [`Name`] of the entity, when the inspector can read it, with the entity
+fn entity_name(env: &mut InspectorUi<'_, '_>, entity: Entity) -> String {
+    let type_registry = env.type_registry;
+    let name = env.context.world.as_mut().and_then(|world| {
+        let name = world
+            .get_entity_component_reflect(entity, TypeId::of::<Name>(), type_registry)
+            .ok()?;
+        name.downcast_ref::<Name>()
+            .map(|name| name.as_str().to_owned())
+    });
+    match name {
+        Some(name) => format!("{name} ({entity})"),
+        None => format!("Entity ({entity})"),
+    }
+}
+
+/// The entities that can be targeted, the ones with a [`GlobalTransform`]
+fn entities_with_global_transforms(env: &InspectorUi<'_, '_>) -> Vec<Entity> {
+    let Some(world) = &env.context.world else {
+        return Vec::new();
+    };
+    let world = world.world();
+    let Some(global_transform) = world.components().component_id::<GlobalTransform>() else {
+        return Vec::new();
+    };
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(global_transform))
+        .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_ecs::reflect::AppTypeRegistry;
+    use bevy_inspector_egui::{
+        inspector_egui_impls::InspectorEguiImpl, prelude::ReflectInspectorOptions,
+    };
+
+    use super::*;
+    use crate::{components::RotateDeadband, RotateTowardsPlugin};
+
+    #[test]
+    fn plugin_registers_the_inspector_options() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        assert!(registry
+            .get_type_data::<InspectorEguiImpl>(TypeId::of::<RotateTarget>())
+            .is_some());
+        assert!(registry
+            .get_type_data::<ReflectInspectorOptions>(TypeId::of::<RotateDeadband>())
+            .is_some());
+    }
+}
@@ -0,0 +1,348 @@
+use core::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::{StaticSystemParam, SystemParam, SystemParamItem},
+};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    components::{RotateTarget, RotateTo},
+    plugin::RotateTowardsSystems,
+};
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(LineOfSightState)]
+/// Gates a [`RotateTo`] on whether the ray from the entity to its target is unobstructed,
+/// as checked by the [`LineOfSightBackend`] of a [`LineOfSightPlugin`]
+///
+/// Without a [`LineOfSightPlugin`], the target is always visible. Add a [`LineOfSightMemory`] to
+/// keep turning towards where the target was last seen.
+pub enum LineOfSight {
+    /// only turns towards the target while it is visible, keeps the rotation otherwise
+    #[default]
+    Track,
+    /// keeps turning towards the target, but is only aligned with it, see
+    /// [`AlignmentThreshold`](crate::AlignmentThreshold), while it is visible
+    Alignment,
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Whether the target of a [`LineOfSight`] is visible, and where it was last seen, kept up to date
+/// by the [`LineOfSightPlugin`], so other systems, like AI, can read it too
+pub struct LineOfSightState {
+    pub visible: bool,
+    /// world space position the target was last visible at
+    pub last_seen: Option<Vec3>,
+    /// how long, in seconds, the target has not been visible
+    pub occluded_secs: f32,
+}
+
+impl Default for LineOfSightState {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            last_seen: None,
+            occluded_secs: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(LineOfSight)]
+/// Keeps a [`LineOfSight::Track`] turning towards where the target was last seen, for a while
+/// after it stops being visible, before keeping its rotation
+pub struct LineOfSightMemory {
+    /// how long, in seconds, the last seen position is remembered
+    pub duration_secs: f32,
+}
+
+impl Default for LineOfSightMemory {
+    fn default() -> Self {
+        Self { duration_secs: 2.0 }
+    }
+}
+
+/// Checks if anything obstructs the ray between two points, like with the spatial queries of a
+/// physics engine, see [`LineOfSightPlugin`]
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SystemParamItem};
+/// # use bevy_math::prelude::*;
+/// # use bevy_app::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// #[derive(Component)]
+/// struct Wall {
+///     x: f32,
+/// }
+///
+/// /// Walls block everything crossing their x
+/// struct Walls;
+///
+/// impl LineOfSightBackend for Walls {
+///     type Param = Query<'static, 'static, &'static Wall>;
+///
+///     fn is_clear(
+///         walls: &SystemParamItem<Self::Param>,
+///         from: Vec3,
+///         to: Vec3,
+///         _rotator: Entity,
+///         _target: Option<Entity>,
+///     ) -> bool {
+///         walls
+///             .iter()
+///             .all(|wall| (from.x - wall.x).signum() == (to.x - wall.x).signum())
+///     }
+/// }
+///
+/// App::new().add_plugins((
+///     RotateTowardsPlugin::default(),
+///     LineOfSightPlugin::<Walls>::default(),
+/// ));
+/// ```
+pub trait LineOfSightBackend: Send + Sync + 'static {
+    /// what the backend needs to cast the ray, like the spatial query parameter of a physics engine
+    type Param: SystemParam;
+
+    /// If the ray from the `rotator` at `from`, to its target at `to`, is unobstructed,
+    /// `target` is `None` when the rotator targets a point
+    fn is_clear(
+        param: &SystemParamItem<Self::Param>,
+        from: Vec3,
+        to: Vec3,
+        rotator: Entity,
+        target: Option<Entity>,
+    ) -> bool;
+}
+
+/// Plugin that keeps the [`LineOfSightState`] of every [`LineOfSight`] up to date with the
+/// [`LineOfSightBackend`] `B`, before the rotation systems run
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`LineOfSightPlugin::in_schedule`].
+pub struct LineOfSightPlugin<B: LineOfSightBackend> {
+    /// the schedule the line of sight is checked in
+    schedule: InternedScheduleLabel,
+    marker: PhantomData<fn() -> B>,
+}
+
+impl<B: LineOfSightBackend> Default for LineOfSightPlugin<B> {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<B: LineOfSightBackend> LineOfSightPlugin<B> {
+    /// Checks the line of sight in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<B: LineOfSightBackend> Plugin for LineOfSightPlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            update_line_of_sight::<B>.in_set(RotateTowardsSystems::Prepare),
+        );
+    }
+}
+
+/// Checks if the target of every [`LineOfSight`] is visible
+fn update_line_of_sight<B: LineOfSightBackend>(
+    mut rotators: Query<(Entity, &RotateTo, &GlobalTransform, &mut LineOfSightState)>,
+    global_transforms: Query<&GlobalTransform>,
+    param: StaticSystemParam<B::Param>,
+    time: Option<Res<Time>>,
+) {
+    let delta_secs = time.as_deref().map_or(0.0, Time::delta_secs);
+    for (rotator, rotate_to, rotator_gt, mut state) in rotators.iter_mut() {
+        let (to, target) = match rotate_to.target {
+            RotateTarget::Entity(target) => {
+                let Ok(target_gt) = global_transforms.get(target) else {
+                    continue;
+                };
+                (rotate_to.aim_point(target_gt), Some(target))
+            }
+            RotateTarget::Point(point) => (point, None),
+            // nothing to be obstructed by
+            RotateTarget::Direction(_) => continue,
+        };
+        let visible = B::is_clear(&param, rotator_gt.translation(), to, rotator, target);
+        let new_state = if visible {
+            LineOfSightState {
+                visible,
+                last_seen: Some(to),
+                occluded_secs: 0.0,
+            }
+        } else {
+            LineOfSightState {
+                visible,
+                occluded_secs: state.occluded_secs + delta_secs,
+                ..*state
+            }
+        };
+        state.set_if_neq(new_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::{
+        components::{AlignmentState, AlignmentThreshold},
+        RotateTowardsPlugin,
+    };
+
+    #[derive(Component)]
+    struct Wall {
+        x: f32,
+    }
+
+    /// Walls block everything crossing their x
+    struct Walls;
+
+    impl LineOfSightBackend for Walls {
+        type Param = Query<'static, 'static, &'static Wall>;
+
+        fn is_clear(
+            walls: &SystemParamItem<Self::Param>,
+            from: Vec3,
+            to: Vec3,
+            _rotator: Entity,
+            _target: Option<Entity>,
+        ) -> bool {
+            walls
+                .iter()
+                .all(|wall| (from.x - wall.x).signum() == (to.x - wall.x).signum())
+        }
+    }
+
+    /// An app with a wall at x 5, whose time advances by a quarter of a second every update
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            RotateTowardsPlugin::new(false),
+            LineOfSightPlugin::<Walls>::default(),
+        ));
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(250));
+        app.insert_resource(time);
+        app.world_mut().spawn(Wall { x: 5.0 });
+        app
+    }
+
+    fn spawn_rotator(app: &mut App, bundle: impl Bundle) -> Entity {
+        app.world_mut()
+            .spawn((Transform::default(), GlobalTransform::default(), bundle))
+            .id()
+    }
+
+    fn forward_of(app: &App, entity: Entity) -> Vec3 {
+        app.world().get::<Transform>(entity).unwrap().rotation * Vec3::NEG_Z
+    }
+
+    fn move_to(app: &mut App, entity: Entity, translation: Vec3) {
+        *app.world_mut().get_mut::<GlobalTransform>(entity).unwrap() =
+            GlobalTransform::from_translation(translation);
+    }
+
+    #[test]
+    fn occluded_targets_are_not_tracked() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(10.0, 0.0, 0.0))
+            .id();
+        let track = spawn_rotator(&mut app, (RotateTo::new(target), LineOfSight::Track));
+        let alignment = spawn_rotator(
+            &mut app,
+            (
+                RotateTo::new(target),
+                LineOfSight::Alignment,
+                AlignmentThreshold {
+                    degrees: 5.0,
+                    trigger_lost: false,
+                },
+            ),
+        );
+        app.update();
+        assert!(!app.world().get::<LineOfSightState>(track).unwrap().visible);
+        assert!(forward_of(&app, track).abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        // turns, but is not aligned while the target is occluded
+        assert!(forward_of(&app, alignment).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(
+            !app.world()
+                .get::<AlignmentState>(alignment)
+                .unwrap()
+                .aligned
+        );
+
+        move_to(&mut app, target, Vec3::new(4.0, 0.0, 0.0));
+        app.update();
+        assert!(forward_of(&app, track).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(
+            app.world()
+                .get::<AlignmentState>(alignment)
+                .unwrap()
+                .aligned
+        );
+    }
+
+    #[test]
+    fn last_seen_position_is_remembered() {
+        let mut app = app();
+        let seen = Vec3::new(4.0, 0.0, 0.0);
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_translation(seen))
+            .id();
+        let rotator = spawn_rotator(
+            &mut app,
+            (
+                RotateTo::new(target),
+                LineOfSightMemory { duration_secs: 0.6 },
+            ),
+        );
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+
+        // behind the wall, off to the side
+        move_to(&mut app, target, Vec3::new(10.0, 0.0, 10.0));
+        app.update();
+        app.update();
+        let state = *app.world().get::<LineOfSightState>(rotator).unwrap();
+        assert!(!state.visible);
+        assert_eq!(state.last_seen, Some(seen));
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+
+        // forgotten, it keeps its rotation
+        for _ in 0..2 {
+            app.update();
+        }
+        app.world_mut()
+            .get_mut::<Transform>(rotator)
+            .unwrap()
+            .rotation = Quat::IDENTITY;
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::NEG_Z, 1e-5));
+    }
+}
@@ -0,0 +1,111 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How often the rotators up to some distance from the reference of the [`RotateLod`] are rotated
+pub struct LodBand {
+    /// the distance from the reference the band reaches up to
+    pub max_distance: f32,
+    /// every how many times the rotation systems run, the rotators within the band are rotated,
+    /// `1` is every time
+    pub interval: u32,
+}
+
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Debug, PartialEq)]
+/// Rotates the rotators less often the further they are from the `reference`, like the camera,
+/// according to the [`LodBand`] they are within, rotators beyond all of them are not rotated
+///
+/// Rotators with their own [`RotateLodBands`] use those instead. The rotators take turns the same
+/// way as with [`RotateTimeSlicing`](crate::RotateTimeSlicing), which they are combined with.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # let camera = Entity::PLACEHOLDER;
+/// // every frame up to 20 units away, every 4th frame up to 60, every 16th up to 200
+/// let lod = RotateLod::new(camera)
+///     .with_band(20.0, 1)
+///     .with_band(60.0, 4)
+///     .with_band(200.0, 16);
+/// ```
+pub struct RotateLod {
+    /// the entity distances are measured from, if it has no
+    /// [`GlobalTransform`](bevy_transform::prelude::GlobalTransform), every rotator is rotated every time
+    pub reference: Entity,
+    /// the bands, ordered by their distance
+    pub bands: Vec<LodBand>,
+}
+
+impl RotateLod {
+    /// Measures the distances from `reference`, add the bands with [`RotateLod::with_band`]
+    pub fn new(reference: Entity) -> Self {
+        Self {
+            reference,
+            bands: Vec::new(),
+        }
+    }
+
+    /// Rotates the rotators up to `max_distance` away, not within a closer band, once every `interval` times
+    pub fn with_band(mut self, max_distance: f32, interval: u32) -> Self {
+        insert_band(&mut self.bands, max_distance, interval);
+        self
+    }
+}
+
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The [`LodBand`]s of this rotator, instead of the ones of the [`RotateLod`], still measured from its reference
+pub struct RotateLodBands(pub Vec<LodBand>);
+
+impl RotateLodBands {
+    /// Rotates the rotator up to `max_distance` away, not within a closer band, once every `interval` times
+    pub fn with_band(mut self, max_distance: f32, interval: u32) -> Self {
+        insert_band(&mut self.0, max_distance, interval);
+        self
+    }
+}
+
+/// Inserts the band, keeping the bands ordered by their distance
+fn insert_band(bands: &mut Vec<LodBand>, max_distance: f32, interval: u32) {
+    let index = bands.partition_point(|band| band.max_distance <= max_distance);
+    bands.insert(
+        index,
+        LodBand {
+            max_distance,
+            interval,
+        },
+    );
+}
+
+/// Every how many times a rotator `distance` away from the reference is rotated, with the
+/// `bands` ordered by their distance, `None` if it is beyond all of them
+pub fn lod_interval(bands: &[LodBand], distance: f32) -> Option<u32> {
+    bands
+        .iter()
+        .find(|band| distance <= band.max_distance)
+        .map(|band| band.interval.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_are_kept_ordered() {
+        let lod = RotateLod::new(Entity::PLACEHOLDER)
+            .with_band(100.0, 3)
+            .with_band(10.0, 1)
+            .with_band(50.0, 0);
+        let distances = lod.bands.iter().map(|band| band.max_distance);
+        assert!(distances.eq([10.0, 50.0, 100.0]));
+        assert_eq!(lod_interval(&lod.bands, 5.0), Some(1));
+        // an interval of 0 still rotates every time
+        assert_eq!(lod_interval(&lod.bands, 20.0), Some(1));
+        assert_eq!(lod_interval(&lod.bands, 100.0), Some(3));
+        assert_eq!(lod_interval(&lod.bands, 101.0), None);
+    }
+}
@@ -0,0 +1,323 @@
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities, system::SystemParam};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::components::RotateTarget;
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, MapEntities)]
+#[reflect(Debug, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An entity of a [`LookChain`], with how much of the look it turns
+pub struct LookChainLink {
+    /// the entity, like a bone, within the hierarchy of the links before it
+    #[entities]
+    pub entity: Entity,
+    /// the part of the look the entity turns, the weights of all the links add up to how much of
+    /// the look is turned, `1.0` fully facing the target
+    pub weight: f32,
+    /// the most the entity turns away from its rest rotation, in degrees, the rest of its part
+    /// is turned by the links after it
+    pub max_degrees: f32,
+}
+
+#[derive(Clone, Component, Debug, PartialEq, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(LookChainState)]
+/// Turns the forward direction of the last of an ordered list of entities, like the head of a
+/// character, towards the target, by splitting the look between all of them, like the spine,
+/// neck and head bones, for a natural gaze instead of turning a single bone all the way
+///
+/// Every link turns from its rest rotation, the one it had before the chain turned it, or the one
+/// an animation sets it to every frame, so the look is split the same way every frame. A link that
+/// reaches its [`LookChainLink::max_degrees`] leaves the rest of its part to the links after it.
+/// The chain turns before the rotators, so a [`RotateTo`](crate::RotateTo) on the eyes rotates
+/// them from where the head is turned to.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn look_at_player(mut commands: Commands, player: Entity, spine: Entity, neck: Entity, head: Entity) {
+///     commands.spawn(
+///         LookChain::new(player)
+///             .with_link(spine, 0.2, 30.0)
+///             .with_link(neck, 0.5, 60.0)
+///             .with_link(head, 0.3, 45.0),
+///     );
+/// }
+/// ```
+pub struct LookChain {
+    /// what to look at, see [`RotateTarget`]
+    #[entities]
+    pub target: RotateTarget,
+    /// the links, from the root of the hierarchy, whose forward direction does not count, to the
+    /// last one, whose forward direction faces the target
+    #[entities]
+    pub links: Vec<LookChainLink>,
+}
+
+/// Looks at [`Entity::PLACEHOLDER`], set the target and add the links before use
+impl Default for LookChain {
+    fn default() -> Self {
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl LookChain {
+    /// Looks at the target, add the links with [`LookChain::with_link`]
+    pub fn new(target: impl Into<RotateTarget>) -> Self {
+        Self {
+            target: target.into(),
+            links: Vec::new(),
+        }
+    }
+
+    /// Adds the `entity` after the links so far, turning `weight` of the look, and no more than
+    /// `max_degrees` away from its rest rotation
+    pub fn with_link(mut self, entity: Entity, weight: f32, max_degrees: f32) -> Self {
+        self.links.push(LookChainLink {
+            entity,
+            weight,
+            max_degrees,
+        });
+        self
+    }
+}
+
+#[derive(Clone, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state [`LookChain`] needs to carry between frames
+pub struct LookChainState {
+    /// the local rotation of every link before the chain turned it
+    pub rest: Vec<Quat>,
+    /// the local rotation the chain turned every link to last time
+    pub turned: Vec<Option<Quat>>,
+}
+
+/// What [`turn_look_chains`] reads, before writing the new rotations
+#[derive(SystemParam)]
+pub(crate) struct LookChainTransforms<'w, 's> {
+    trans_helper: TransformHelper<'w, 's>,
+    transforms: Query<'w, 's, (&'static Transform, Option<&'static ChildOf>)>,
+}
+
+impl LookChainTransforms<'_, '_> {
+    /// The global transform of the entity, with every link of the chain at its rest rotation
+    fn rest_global_transform(
+        &self,
+        entity: Entity,
+        links: &[LookChainLink],
+        state: &LookChainState,
+    ) -> Option<GlobalTransform> {
+        let mut ancestors = Vec::new();
+        let mut next = Some(entity);
+        while let Some(entity) = next {
+            let (transform, child_of) = self.transforms.get(entity).ok()?;
+            let rest = links.iter().position(|link| link.entity == entity);
+            ancestors.push(match rest {
+                Some(index) => transform.with_rotation(state.rest[index]),
+                None => *transform,
+            });
+            next = child_of.map(ChildOf::parent);
+        }
+        Some(
+            ancestors
+                .into_iter()
+                .rev()
+                .fold(GlobalTransform::IDENTITY, |parent_gt, transform| {
+                    parent_gt * transform
+                }),
+        )
+    }
+}
+
+/// Splits the look of every [`LookChain`] between its links
+pub(crate) fn turn_look_chains(
+    mut chains: Query<(&LookChain, &mut LookChainState)>,
+    mut params: ParamSet<(LookChainTransforms, Query<&mut Transform>)>,
+    mut rotations: Local<Vec<(Entity, Quat)>>,
+) {
+    // the rotations are computed first, since they depend on the transforms being written
+    let transforms = params.p0();
+    for (chain, mut state) in chains.iter_mut() {
+        let links = &chain.links;
+        let Some(last) = links.last() else {
+            continue;
+        };
+        let state = &mut *state;
+        state.rest.resize(links.len(), Quat::IDENTITY);
+        state.turned.resize(links.len(), None);
+        // a link that was rotated by something else, like an animation, rests there
+        for (index, link) in links.iter().enumerate() {
+            let Ok((transform, _)) = transforms.transforms.get(link.entity) else {
+                continue;
+            };
+            if state.turned[index] != Some(transform.rotation) {
+                state.rest[index] = transform.rotation;
+            }
+        }
+
+        let Some(last_gt) = transforms.rest_global_transform(last.entity, links, state) else {
+            continue;
+        };
+        let direction = match chain.target {
+            RotateTarget::Entity(entity) => {
+                let Ok(target_gt) = transforms.trans_helper.compute_global_transform(entity) else {
+                    continue;
+                };
+                target_gt.translation() - last_gt.translation()
+            }
+            RotateTarget::Point(point) => point - last_gt.translation(),
+            RotateTarget::Direction(direction) => *direction,
+        };
+        let Ok(direction) = Dir3::new(direction) else {
+            continue;
+        };
+        let (axis, angle) = Quat::from_rotation_arc(*last_gt.forward(), *direction).to_axis_angle();
+
+        let mut weights: f32 = links.iter().map(|link| link.weight.max(0.0)).sum();
+        let mut remaining = angle * weights.min(1.0);
+        for (index, link) in links.iter().enumerate() {
+            let weight = link.weight.max(0.0);
+            let part = match weights > 0.0 {
+                true => remaining * weight / weights,
+                false => 0.0,
+            };
+            let turn = part.min(link.max_degrees.max(0.0).to_radians());
+            remaining -= turn;
+            weights -= weight;
+
+            // every link turns around the same world space axis, so the turns of the links
+            // before it do not change how it turns, within the space of its rested parent
+            let parent_rotation = transforms
+                .transforms
+                .get(link.entity)
+                .ok()
+                .and_then(|(_, child_of)| child_of)
+                .and_then(|child_of| {
+                    transforms.rest_global_transform(child_of.parent(), links, state)
+                })
+                .map_or(Quat::IDENTITY, |parent_gt| parent_gt.rotation());
+            let world_turn = Quat::from_axis_angle(axis, turn);
+            let rotation =
+                (parent_rotation.inverse() * world_turn * parent_rotation * state.rest[index])
+                    .normalize();
+            state.turned[index] = Some(rotation);
+            rotations.push((link.entity, rotation));
+        }
+    }
+
+    let mut transforms = params.p1();
+    for (entity, rotation) in rotations.drain(..) {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.set_if_neq(transform.with_rotation(rotation));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_4;
+
+    use bevy_app::prelude::*;
+
+    use super::*;
+    use crate::RotateTowardsPlugin;
+
+    /// Spawns a chain of a spine at the origin, a neck above it and a head above that, looking at a
+    /// point to the right of the head, a quarter turn away
+    fn spawn_chain(app: &mut App, max_degrees: [f32; 3]) -> [Entity; 3] {
+        let head = app
+            .world_mut()
+            .spawn(Transform::from_xyz(0.0, 1.0, 0.0))
+            .id();
+        let neck = app
+            .world_mut()
+            .spawn(Transform::from_xyz(0.0, 1.0, 0.0))
+            .add_child(head)
+            .id();
+        let spine = app
+            .world_mut()
+            .spawn(Transform::default())
+            .add_child(neck)
+            .id();
+        app.world_mut().spawn(
+            LookChain::new(Vec3::new(10.0, 2.0, 0.0))
+                .with_link(spine, 0.2, max_degrees[0])
+                .with_link(neck, 0.5, max_degrees[1])
+                .with_link(head, 0.3, max_degrees[2]),
+        );
+        [spine, neck, head]
+    }
+
+    fn rotation_of(app: &App, entity: Entity) -> Quat {
+        app.world().get::<Transform>(entity).unwrap().rotation
+    }
+
+    fn degrees_of(app: &App, entity: Entity) -> f32 {
+        rotation_of(app, entity)
+            .angle_between(Quat::IDENTITY)
+            .to_degrees()
+    }
+
+    #[test]
+    fn last_link_faces_the_target() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let links = spawn_chain(&mut app, [90.0; 3]);
+        app.update();
+        let rotation = links.iter().fold(Quat::IDENTITY, |rotation, &link| {
+            rotation * rotation_of(&app, link)
+        });
+        let forward = rotation * Vec3::NEG_Z;
+        assert!(forward.abs_diff_eq(Vec3::X, 1e-5), "{forward}");
+    }
+
+    #[test]
+    fn links_turn_their_part_of_the_look() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let [spine, neck, head] = spawn_chain(&mut app, [90.0; 3]);
+        app.update();
+        for (link, degrees) in [(spine, 18.0), (neck, 45.0), (head, 27.0)] {
+            let turned = degrees_of(&app, link);
+            assert!((turned - degrees).abs() < 1e-3, "{turned}");
+        }
+
+        // the neck leaves the rest of its part to the head
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let [spine, neck, head] = spawn_chain(&mut app, [90.0, 30.0, 90.0]);
+        app.update();
+        for (link, degrees) in [(spine, 18.0), (neck, 30.0), (head, 42.0)] {
+            let turned = degrees_of(&app, link);
+            assert!((turned - degrees).abs() < 1e-3, "{turned}");
+        }
+    }
+
+    #[test]
+    fn animated_rotation_is_the_new_rest() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let head = app.world_mut().spawn(Transform::default()).id();
+        let chain = app
+            .world_mut()
+            .spawn(LookChain::new(Vec3::X).with_link(head, 0.5, 180.0))
+            .id();
+        app.update();
+        let turned = degrees_of(&app, head);
+        assert!((turned - 45.0).abs() < 1e-3, "{turned}");
+
+        let animated = Quat::from_rotation_y(FRAC_PI_4);
+        app.world_mut().get_mut::<Transform>(head).unwrap().rotation = animated;
+        for _ in 0..2 {
+            app.update();
+            let state = app.world().get::<LookChainState>(chain).unwrap();
+            assert_eq!(state.rest, [animated]);
+            let turned = rotation_of(&app, head).angle_between(animated).to_degrees();
+            assert!((turned - 67.5).abs() < 1e-3, "{turned}");
+        }
+    }
+}
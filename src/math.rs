@@ -0,0 +1,365 @@
+use core::f32::consts::PI;
+
+use bevy_math::{ops, prelude::*};
+use bevy_transform::prelude::*;
+
+/// Turns a world space direction into the space of the eventual parent, so the parent turns it
+/// back along the world space direction, with any scale, shear or mirroring of the parent, rather
+/// than only its rotation
+pub(crate) fn direction_in_parent(direction: Vec3, parent_gt: Option<&GlobalTransform>) -> Vec3 {
+    let Some(parent_gt) = parent_gt else {
+        return direction;
+    };
+    let matrix = parent_gt.affine().matrix3;
+    // a parent scaled to nothing has no space to turn the direction into
+    if matrix.determinant().abs() <= f32::EPSILON {
+        return direction;
+    }
+    matrix.inverse() * direction
+}
+
+/// Turns a direction in the space of the eventual parent into world space, keeping its length,
+/// the inverse of [`direction_in_parent`], without decomposing the parent into its rotation
+pub(crate) fn direction_from_parent(direction: Vec3, parent_gt: Option<&GlobalTransform>) -> Vec3 {
+    let Some(parent_gt) = parent_gt else {
+        return direction;
+    };
+    let turned = parent_gt.affine().matrix3 * direction;
+    turned.normalize_or_zero() * direction.length()
+}
+
+/// The local rotation of a rotator looking along a world space direction, in the space of its
+/// eventual parent, with the selected rotator up direction
+fn local_look_to(direction: Vec3, parent_gt: Option<&GlobalTransform>, updir: Dir3) -> Quat {
+    // the parent turns the plane of the local direction and up into the plane of the world space
+    // ones, so the rotator faces along `direction`, with its up on the side of `updir`
+    let direction = direction_in_parent(direction, parent_gt);
+    let updir = direction_in_parent(updir.as_vec3(), parent_gt);
+    Transform::default().looking_to(direction, updir).rotation
+}
+
+/// Calculates the local rotation on a rotator towards a target,
+/// adjusting for the rotation, scale and mirroring of eventual parents,
+/// with the selected rotator up direction.
+pub fn calculate_local_rotation_to_target(
+    rotator_gt: &GlobalTransform,
+    target_gt: &GlobalTransform,
+    parent_gt: Option<&GlobalTransform>,
+    updir: Dir3,
+) -> Quat {
+    calculate_local_rotation_to_point(rotator_gt, target_gt.translation(), parent_gt, updir)
+}
+
+/// Calculates the local rotation on a rotator towards a world space position,
+/// adjusting for the rotation, scale and mirroring of eventual parents,
+/// with the selected rotator up direction.
+pub fn calculate_local_rotation_to_point(
+    rotator_gt: &GlobalTransform,
+    target_pos: Vec3,
+    parent_gt: Option<&GlobalTransform>,
+    updir: Dir3,
+) -> Quat {
+    // only the translation of the rotator matters, so it is not decomposed
+    local_look_to(target_pos - rotator_gt.translation(), parent_gt, updir)
+}
+
+/// Calculates the local rotation on a rotator looking along a world space direction,
+/// adjusting for the rotation, scale and mirroring of eventual parents,
+/// with the selected rotator up direction.
+pub fn calculate_local_rotation_to_direction(
+    direction: Dir3,
+    parent_gt: Option<&GlobalTransform>,
+    updir: Dir3,
+) -> Quat {
+    local_look_to(direction.as_vec3(), parent_gt, updir)
+}
+
+/// Calculates the local rotation on a rotator that can only turn around `axis`, in the space of its
+/// eventual parent, bringing its forward direction as close to a world space direction as it can.
+///
+/// The direction is projected onto the plane the rotator turns in, returns `None` if it has
+/// nothing left, or if `Vec3::NEG_Z` does not leave the plane either.
+pub fn calculate_local_rotation_around_axis(
+    direction: Vec3,
+    parent_gt: Option<&GlobalTransform>,
+    axis: Dir3,
+) -> Option<Quat> {
+    let direction = direction_in_parent(direction, parent_gt);
+    let from = Vec3::NEG_Z.reject_from_normalized(*axis).try_normalize()?;
+    let to = direction.reject_from_normalized(*axis).try_normalize()?;
+    let angle = ops::atan2(axis.dot(from.cross(to)), from.dot(to));
+    Some(Quat::from_axis_angle(*axis, angle))
+}
+
+/// Calculates the local rotation around Z on a 2D rotator towards a world space XY position,
+/// adjusting for the rotation, scale and mirroring of eventual parents,
+/// with the art of the rotator facing `angle_offset_degrees` counterclockwise from `Vec3::X`.
+///
+/// Returns `None` if the target is at the XY position of the rotator.
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use bevy_mod_lookat::*;
+/// let rotator_gt = GlobalTransform::from_xyz(1.0, 1.0, 0.0);
+/// let rotation = calculate_rotation_to_target_2d(&rotator_gt, Vec2::new(1.0, 5.0), None, 90.0);
+/// assert!(rotation.unwrap().angle_between(Quat::IDENTITY) < 1e-5);
+/// ```
+pub fn calculate_rotation_to_target_2d(
+    rotator_gt: &GlobalTransform,
+    target_pos: Vec2,
+    parent_gt: Option<&GlobalTransform>,
+    angle_offset_degrees: f32,
+) -> Option<Quat> {
+    calculate_rotation_to_direction_2d(
+        target_pos - rotator_gt.translation().xy(),
+        parent_gt,
+        angle_offset_degrees,
+    )
+}
+
+/// Calculates the local rotation around Z on a 2D rotator looking along a world space XY direction,
+/// adjusting for the rotation, scale and mirroring of eventual parents,
+/// with the art of the rotator facing `angle_offset_degrees` counterclockwise from `Vec3::X`.
+///
+/// Returns `None` if the direction is zero.
+pub fn calculate_rotation_to_direction_2d(
+    direction: Vec2,
+    parent_gt: Option<&GlobalTransform>,
+    angle_offset_degrees: f32,
+) -> Option<Quat> {
+    let direction = direction.try_normalize()?;
+    let angle_offset = angle_offset_degrees.to_radians();
+    if parent_gt.is_none() {
+        return Some(Quat::from_rotation_z(direction.to_angle() - angle_offset));
+    }
+
+    // turn the art, with Z towards the camera, to the direction in the space of the parent,
+    // with the parent turning Z back towards the camera
+    let art = Transform::default()
+        .looking_to(Quat::from_rotation_z(angle_offset) * Vec3::X, Dir3::Z)
+        .rotation;
+    Some(local_look_to(direction.extend(0.0), parent_gt, Dir3::Z) * art.inverse())
+}
+
+/// Splits a rotation into its swing and its twist around `axis`, so that
+/// `rotation == swing * twist`, the twist only turns around `axis`,
+/// and the swing turns `axis` to where `rotation` turns it, without turning around it.
+///
+/// Useful to split the yaw from the pitch of a rotation, or to limit them on their own.
+/// A rotation turning `axis` straight around has no twist.
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::*;
+/// let yaw = Quat::from_rotation_y(0.5);
+/// let pitch = Quat::from_rotation_x(0.3);
+/// let rotation = yaw * pitch;
+/// let (swing, twist) = swing_twist(rotation, Dir3::Y);
+/// assert!(twist.abs_diff_eq(yaw, 1e-5));
+/// assert!((swing * twist).abs_diff_eq(rotation, 1e-5));
+/// // the pitch, after the yaw
+/// assert!((twist.inverse() * rotation).abs_diff_eq(pitch, 1e-5));
+/// ```
+pub fn swing_twist(rotation: Quat, axis: Dir3) -> (Quat, Quat) {
+    let projected = rotation.xyz().project_onto_normalized(*axis);
+    let twist = projected
+        .extend(rotation.w)
+        .try_normalize()
+        .map_or(Quat::IDENTITY, Quat::from_vec4);
+    (rotation * twist.inverse(), twist)
+}
+
+/// The signed angle, in radians, a rotation turns around `axis`, the angle of the twist from
+/// [`swing_twist`]
+pub fn twist_angle(rotation: Quat, axis: Dir3) -> f32 {
+    let (_, twist) = swing_twist(rotation, axis);
+    let angle = 2.0 * ops::atan2(twist.xyz().dot(*axis), twist.w);
+    // keep the angle within -PI..=PI
+    if angle > PI {
+        angle - 2.0 * PI
+    } else if angle < -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+/// The time, in seconds, until a projectile fired at `projectile_speed` can hit a target at
+/// `relative_position` from where it is fired, that moves at `velocity`,
+/// `None` if the projectile can never catch up to the target
+///
+/// The target is then hit at `relative_position + velocity * time`.
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::*;
+/// // the target moves sideways, at 3 units per second, 20 units away
+/// let time = calculate_intercept_time(Vec3::new(0.0, 0.0, -20.0), Vec3::X * 3.0, 5.0).unwrap();
+/// let hit = Vec3::new(0.0, 0.0, -20.0) + Vec3::X * 3.0 * time;
+/// assert!((hit.length() - 5.0 * time).abs() < 1e-3);
+/// // a target moving away faster than the projectile
+/// assert!(calculate_intercept_time(Vec3::NEG_Z, Vec3::NEG_Z * 10.0, 5.0).is_none());
+/// ```
+pub fn calculate_intercept_time(
+    relative_position: Vec3,
+    velocity: Vec3,
+    projectile_speed: f32,
+) -> Option<f32> {
+    // where |relative_position + velocity * t| == projectile_speed * t
+    let a = velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2.0 * relative_position.dot(velocity);
+    let c = relative_position.length_squared();
+    if a.abs() < f32::EPSILON {
+        // as fast as the target, only hits it while closing in
+        let time = -c / b;
+        return (time >= 0.0 && time.is_finite()).then_some(time);
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = ops::sqrt(discriminant);
+    let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+    // the earliest hit that is not in the past
+    match (t1.min(t2), t1.max(t2)) {
+        (time, _) if time >= 0.0 => Some(time),
+        (_, time) if time >= 0.0 => Some(time),
+        _ => None,
+    }
+}
+
+/// The angular velocity, in radians per second, that turns `from` into `to` over `delta_secs`
+/// along the shortest way, as a rotation axis scaled by the speed, zero if no time passes
+///
+/// ```
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::*;
+/// let velocity = calculate_angular_velocity(Quat::IDENTITY, Quat::from_rotation_y(0.5), 0.25);
+/// assert!(velocity.abs_diff_eq(Vec3::Y * 2.0, 1e-5));
+/// ```
+pub fn calculate_angular_velocity(from: Quat, to: Quat, delta_secs: f32) -> Vec3 {
+    if delta_secs <= 0.0 {
+        return Vec3::ZERO;
+    }
+    let mut delta = to * from.inverse();
+    // -q is the same rotation, the other way around
+    if delta.w < 0.0 {
+        delta = -delta;
+    }
+    delta.to_scaled_axis() / delta_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Where the parent turns the local forward of the rotator, in world space
+    fn world_forward(parent_gt: &GlobalTransform, local: Quat) -> Vec3 {
+        parent_gt
+            .affine()
+            .transform_vector3(local * Vec3::NEG_Z)
+            .normalize()
+    }
+
+    fn parents() -> [GlobalTransform; 4] {
+        [
+            // stretched
+            GlobalTransform::from_scale(Vec3::new(3.0, 1.0, 0.5)),
+            // mirrored
+            GlobalTransform::from_scale(Vec3::new(-1.0, 1.0, 1.0)),
+            // mirrored, stretched and rotated
+            GlobalTransform::from(
+                Transform::from_rotation(Quat::from_rotation_y(0.7))
+                    .with_scale(Vec3::new(2.0, -0.5, 1.0)),
+            ),
+            // sheared, like a rotated child of a stretched parent
+            GlobalTransform::from_scale(Vec3::new(2.0, 1.0, 1.0))
+                * GlobalTransform::from_rotation(Quat::from_rotation_z(0.4)),
+        ]
+    }
+
+    #[test]
+    fn point_under_scaled_and_mirrored_parents() {
+        let target = Vec3::new(4.0, 2.0, -3.0);
+        for parent_gt in parents() {
+            let rotator_gt = parent_gt * GlobalTransform::from_xyz(0.5, 0.0, 0.0);
+            let local =
+                calculate_local_rotation_to_point(&rotator_gt, target, Some(&parent_gt), Dir3::Y);
+            let expected = (target - rotator_gt.translation()).normalize();
+            let forward = world_forward(&parent_gt, local);
+            assert!(forward.dot(expected) > 0.9999, "{parent_gt:?} {forward}");
+            // the up of the rotator stays on the side of the up direction
+            let up = parent_gt.affine().transform_vector3(local * Vec3::Y);
+            assert!(up.dot(Vec3::Y) > 0.0, "{parent_gt:?} {up}");
+        }
+    }
+
+    #[test]
+    fn direction_under_scaled_and_mirrored_parents() {
+        let direction = Dir3::new(Vec3::new(-1.0, 0.5, 2.0)).unwrap();
+        for parent_gt in parents() {
+            let local = calculate_local_rotation_to_direction(direction, Some(&parent_gt), Dir3::Y);
+            let forward = world_forward(&parent_gt, local);
+            assert!(forward.dot(*direction) > 0.9999, "{parent_gt:?} {forward}");
+        }
+    }
+
+    #[test]
+    fn axis_under_mirrored_parent() {
+        let parent_gt = GlobalTransform::from_scale(Vec3::new(-1.0, 1.0, 1.0));
+        let local = calculate_local_rotation_around_axis(
+            Vec3::new(1.0, 0.0, -1.0),
+            Some(&parent_gt),
+            Dir3::Y,
+        )
+        .unwrap();
+        let forward = world_forward(&parent_gt, local);
+        assert!(
+            forward.dot(Vec3::new(1.0, 0.0, -1.0).normalize()) > 0.9999,
+            "{forward}"
+        );
+    }
+
+    #[test]
+    fn direction_2d_under_mirrored_parent() {
+        let parent_gt = GlobalTransform::from_scale(Vec3::new(-1.0, 2.0, 1.0));
+        let direction = Vec2::new(1.0, 1.0);
+        let local = calculate_rotation_to_direction_2d(direction, Some(&parent_gt), 90.0).unwrap();
+        // the art faces up, along Y
+        let art = parent_gt.affine().transform_vector3(local * Vec3::Y);
+        assert!(
+            art.normalize().dot(direction.normalize().extend(0.0)) > 0.9999,
+            "{art}"
+        );
+    }
+
+    #[test]
+    fn rotated_parent_is_unchanged() {
+        let parent_gt = GlobalTransform::from_rotation(Quat::from_rotation_y(1.0));
+        let direction = Dir3::new(Vec3::new(1.0, 1.0, 0.0)).unwrap();
+        let local = calculate_local_rotation_to_direction(direction, Some(&parent_gt), Dir3::Y);
+        let world = Transform::default().looking_to(direction, Dir3::Y).rotation;
+        assert!((parent_gt.rotation() * local).abs_diff_eq(world, 1e-5));
+        let local_2d = calculate_rotation_to_direction_2d(Vec2::Y, Some(&parent_gt), 0.0).unwrap();
+        let world_2d = Quat::from_rotation_z(core::f32::consts::FRAC_PI_2);
+        assert!((parent_gt.rotation() * local_2d).abs_diff_eq(world_2d, 1e-5));
+    }
+
+    #[test]
+    fn direction_from_parent_undoes_direction_in_parent() {
+        let direction = Vec3::new(1.0, -2.0, 0.5);
+        for parent_gt in parents() {
+            let local = direction_in_parent(direction, Some(&parent_gt));
+            let world = direction_from_parent(local, Some(&parent_gt));
+            assert!(world.normalize().abs_diff_eq(direction.normalize(), 1e-5));
+            assert!((world.length() - local.length()).abs() < 1e-5);
+        }
+        // rotated and evenly scaled, the parent turns it like its rotation does
+        let parent_gt = GlobalTransform::from(
+            Transform::from_rotation(Quat::from_rotation_y(0.7)).with_scale(Vec3::splat(3.0)),
+        );
+        let world = direction_from_parent(direction, Some(&parent_gt));
+        assert!(world.abs_diff_eq(parent_gt.rotation() * direction, 1e-5));
+    }
+}
@@ -0,0 +1,313 @@
+use bevy_ecs::{lifecycle::HookContext, prelude::*, world::DeferredWorld};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::prelude::*;
+
+use crate::components::{RotateTarget, RotateTo};
+
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting the entity with this [`Name`], so look at
+/// setups can be authored in assets, without references to entities that do not exist yet
+///
+/// The target is found again whenever names change, like when a scene or prefab spawns, or the
+/// target is despawned. Of several entities with the name, the lowest one is targeted, while
+/// there is none, the entity keeps its rotation, see [`Entity::PLACEHOLDER`].
+///
+/// ```
+/// # use bevy_mod_lookat::prelude::*;
+/// // a guard spawned before the player looks at it once it spawns
+/// let guard = RotateToNamed::new("Player");
+/// ```
+pub struct RotateToNamed(pub String);
+
+impl RotateToNamed {
+    /// Targets the entity with the [`Name`] `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting an entity in its own scene or prefab instance,
+/// found by the [`Name`]s along the path to it, separated by `/`, so every instance targets its
+/// own nodes, unlike with a [`RotateToNamed`]
+///
+/// The path is followed down the children of the closest ancestor of the entity it leads from,
+/// like the root of a glTF instance it was spawned with, of several children with a name, the
+/// lowest one is followed. The target is found again whenever names or the hierarchy change,
+/// while there is none, the entity keeps its rotation, see [`Entity::PLACEHOLDER`].
+///
+/// ```
+/// # use bevy_mod_lookat::prelude::*;
+/// // the eyes of every instance of a character look at the hand of the same instance
+/// let eyes = RotateToPath::new("Armature/Hand");
+/// ```
+pub struct RotateToPath(pub String);
+
+impl RotateToPath {
+    /// Targets the entity the [`Name`]s along `path`, separated by `/`, lead to
+    pub fn new(path: impl Into<String>) -> Self {
+        Self(path.into())
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[component(on_insert = register_persistent_id, on_replace = unregister_persistent_id)]
+/// An id of the entity that stays the same across saving and loading, unlike the [`Entity`], for
+/// a [`RotateToPersistent`] to target, like a hash of a UUID
+///
+/// Every id should be on a single entity, the entity of every id is kept in the
+/// [`PersistentTargets`] resource.
+pub struct PersistentId(pub u64);
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(RotateTo)]
+/// Keeps the [`RotateTo`] of the entity targeting the entity with this [`PersistentId`], so it
+/// targets the same entity again after a save game is loaded, when all entities are new
+///
+/// The target is found again whenever an id is added or removed, while there is none, the entity
+/// keeps its rotation, see [`Entity::PLACEHOLDER`].
+pub struct RotateToPersistent(pub PersistentId);
+
+#[derive(Resource, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+/// The entity of every [`PersistentId`], kept up to date as ids are added and removed
+pub struct PersistentTargets(HashMap<PersistentId, Entity>);
+
+impl PersistentTargets {
+    /// The entity with the `id`, if any
+    pub fn get(&self, id: PersistentId) -> Option<Entity> {
+        self.0.get(&id).copied()
+    }
+}
+
+fn register_persistent_id(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(&id) = world.get::<PersistentId>(entity) else {
+        return;
+    };
+    if let Some(mut targets) = world.get_resource_mut::<PersistentTargets>() {
+        targets.0.insert(id, entity);
+    }
+}
+
+fn unregister_persistent_id(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(&id) = world.get::<PersistentId>(entity) else {
+        return;
+    };
+    if let Some(mut targets) = world.get_resource_mut::<PersistentTargets>() {
+        if targets.0.get(&id) == Some(&entity) {
+            targets.0.remove(&id);
+        }
+    }
+}
+
+/// Binds every [`RotateToNamed`] to the entity with its name
+pub(crate) fn resolve_named_targets(
+    mut rotators: Query<(Ref<RotateToNamed>, &mut RotateTo)>,
+    names: Query<(Entity, &Name)>,
+    changed_names: Query<(), Changed<Name>>,
+    mut removed_names: RemovedComponents<Name>,
+    mut by_name: Local<HashMap<String, Entity>>,
+) {
+    let names_changed = !changed_names.is_empty() || removed_names.read().count() > 0;
+    if names_changed {
+        by_name.clear();
+        for (entity, name) in names.iter() {
+            by_name
+                .entry(name.as_str().to_owned())
+                .and_modify(|target| *target = (*target).min(entity))
+                .or_insert(entity);
+        }
+    }
+    for (named, mut rotate_to) in rotators.iter_mut() {
+        if !names_changed && !named.is_changed() {
+            continue;
+        }
+        let target = RotateTarget::Entity(
+            by_name
+                .get(named.0.as_str())
+                .copied()
+                .unwrap_or(Entity::PLACEHOLDER),
+        );
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+/// Filters for entities whose name or parent changed
+type HierarchyChanged = Or<(Changed<Name>, Changed<ChildOf>)>;
+
+/// Binds every [`RotateToPath`] to the entity its path leads to, from the closest ancestor
+pub(crate) fn resolve_path_targets(
+    mut rotators: Query<(Entity, Ref<RotateToPath>, &mut RotateTo)>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    changed: Query<(), HierarchyChanged>,
+    mut removed_names: RemovedComponents<Name>,
+    mut removed_parents: RemovedComponents<ChildOf>,
+) {
+    let hierarchy_changed = !changed.is_empty()
+        || removed_names.read().count() > 0
+        || removed_parents.read().count() > 0;
+    // the lowest child of `parent` named `name`
+    let child_named = |parent: Entity, name: &str| {
+        children
+            .get(parent)
+            .into_iter()
+            .flatten()
+            .filter(|&&child| names.get(child).is_ok_and(|n| n.as_str() == name))
+            .min()
+            .copied()
+    };
+    for (rotator, path, mut rotate_to) in rotators.iter_mut() {
+        if !hierarchy_changed && !path.is_changed() {
+            continue;
+        }
+        let target = parents
+            .iter_ancestors(rotator)
+            .find_map(|ancestor| {
+                path.0
+                    .split('/')
+                    .filter(|name| !name.is_empty())
+                    .try_fold(ancestor, child_named)
+            })
+            .unwrap_or(Entity::PLACEHOLDER);
+        let target = RotateTarget::Entity(target);
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+/// Binds every [`RotateToPersistent`] to the entity with its id
+pub(crate) fn resolve_persistent_targets(
+    mut rotators: Query<(Ref<RotateToPersistent>, &mut RotateTo)>,
+    targets: Res<PersistentTargets>,
+) {
+    for (persistent, mut rotate_to) in rotators.iter_mut() {
+        if !targets.is_changed() && !persistent.is_changed() {
+            continue;
+        }
+        let target = RotateTarget::Entity(targets.get(persistent.0).unwrap_or(Entity::PLACEHOLDER));
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_transform::prelude::*;
+
+    use super::*;
+    use crate::RotateTowardsPlugin;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        app
+    }
+
+    fn spawn_named(app: &mut App, name: &str) -> Entity {
+        app.world_mut()
+            .spawn((GlobalTransform::default(), Name::new(name.to_owned())))
+            .id()
+    }
+
+    fn target_of(app: &App, rotator: Entity) -> RotateTarget {
+        app.world().get::<RotateTo>(rotator).unwrap().target
+    }
+
+    #[test]
+    fn named_target_is_found_again() {
+        let mut app = app();
+        let first = spawn_named(&mut app, "Player");
+        let rotator = app
+            .world_mut()
+            .spawn((Transform::default(), RotateToNamed::new("Player")))
+            .id();
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(first));
+
+        app.world_mut().despawn(first);
+        let second = spawn_named(&mut app, "Player");
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(second));
+
+        app.world_mut()
+            .entity_mut(second)
+            .insert(Name::new("Enemy"));
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::default());
+    }
+
+    /// Spawns a named child of `parent`
+    fn spawn_child(app: &mut App, parent: Entity, name: &str) -> Entity {
+        let child = spawn_named(app, name);
+        app.world_mut().entity_mut(parent).add_child(child);
+        child
+    }
+
+    #[test]
+    fn path_is_followed_from_the_closest_ancestor() {
+        let mut app = app();
+        // two instances of a character, whose eyes look at the hand of the same instance
+        let [(hand_a, body_a, eyes_a), (hand_b, _, eyes_b)] = [(); 2].map(|_| {
+            let root = spawn_named(&mut app, "Character");
+            let armature = spawn_child(&mut app, root, "Armature");
+            let hand = spawn_child(&mut app, armature, "Hand");
+            let body = spawn_child(&mut app, root, "Body");
+            let eyes = app
+                .world_mut()
+                .spawn((Transform::default(), RotateToPath::new("Armature/Hand")))
+                .id();
+            app.world_mut().entity_mut(body).add_child(eyes);
+            (hand, body, eyes)
+        });
+        app.update();
+        assert_eq!(target_of(&app, eyes_a), RotateTarget::Entity(hand_a));
+        assert_eq!(target_of(&app, eyes_b), RotateTarget::Entity(hand_b));
+
+        // a closer ancestor the path leads from
+        let armature = spawn_child(&mut app, body_a, "Armature");
+        let closer_hand = spawn_child(&mut app, armature, "Hand");
+        app.update();
+        assert_eq!(target_of(&app, eyes_a), RotateTarget::Entity(closer_hand));
+        assert_eq!(target_of(&app, eyes_b), RotateTarget::Entity(hand_b));
+    }
+
+    #[test]
+    fn persistent_target_is_found_after_loading() {
+        let mut app = app();
+        let id = PersistentId(7);
+        let saved = app.world_mut().spawn((GlobalTransform::default(), id)).id();
+        let rotator = app
+            .world_mut()
+            .spawn((Transform::default(), RotateToPersistent(id)))
+            .id();
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(saved));
+
+        app.world_mut().despawn(saved);
+        app.update();
+        assert_eq!(target_of(&app, rotator), RotateTarget::default());
+
+        // loading the save game spawns the target again, as a new entity
+        let loaded = app.world_mut().spawn((GlobalTransform::default(), id)).id();
+        app.update();
+        assert_ne!(loaded, saved);
+        assert_eq!(target_of(&app, rotator), RotateTarget::Entity(loaded));
+    }
+}
@@ -0,0 +1,222 @@
+use core::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
+use bevy_time::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    components::{RotateTarget, RotateTo},
+    plugin::RotateTowardsSystems,
+};
+
+/// Keeps the [`RotateTo`] of the entity targeting the nearest entity with the component `M`,
+/// with the [`RotateToNearestPlugin`] of `M`, like the nearest enemy for a simple auto aim
+///
+/// While there is none, the entity keeps its rotation, see [`Entity::PLACEHOLDER`]. For selecting
+/// targets by group, range or priority, see [`AutoTarget`](crate::AutoTarget).
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// App::new().add_plugins((
+///     RotateTowardsPlugin::default(),
+///     RotateToNearestPlugin::<Enemy>::default().with_interval(0.25),
+/// ));
+///
+/// fn spawn_turret(mut commands: Commands) {
+///     commands.spawn(RotateToNearest::<Enemy>::default());
+/// }
+/// ```
+#[derive(Component)]
+#[require(RotateTo)]
+pub struct RotateToNearest<M: Component> {
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<M: Component> Default for RotateToNearest<M> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Plugin that keeps every [`RotateToNearest`] of `M` targeting the nearest entity with `M`,
+/// comparing the distances every time the rotation systems run, or every
+/// [`RotateToNearestPlugin::with_interval`] seconds
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`RotateToNearestPlugin::in_schedule`].
+pub struct RotateToNearestPlugin<M: Component> {
+    /// the schedule the targets are selected in
+    schedule: InternedScheduleLabel,
+    /// how many seconds there are between comparing the distances
+    interval: f32,
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<M: Component> Default for RotateToNearestPlugin<M> {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            interval: 0.0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Component> RotateToNearestPlugin<M> {
+    /// Selects the targets in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Compares the distances once every `seconds`, new rotators select their target right away
+    pub fn with_interval(mut self, seconds: f32) -> Self {
+        self.interval = seconds;
+        self
+    }
+}
+
+impl<M: Component> Plugin for RotateToNearestPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(NearestTimer::<M> {
+            interval: self.interval,
+            elapsed: None,
+            marker: PhantomData,
+        })
+        .add_systems(
+            self.schedule,
+            rotate_to_nearest::<M>
+                .run_if(any_with_component::<RotateToNearest<M>>)
+                .in_set(RotateTowardsSystems::Retarget),
+        );
+    }
+}
+
+/// When the [`RotateToNearestPlugin`] of `M` compares the distances next
+#[derive(Resource)]
+struct NearestTimer<M: Component> {
+    /// how many seconds there are between comparing the distances
+    interval: f32,
+    /// how many seconds passed since the distances were compared, `None` before the first time
+    elapsed: Option<f32>,
+    marker: PhantomData<fn() -> M>,
+}
+
+/// Targets the nearest entity with `M` from every [`RotateToNearest`] of `M`
+fn rotate_to_nearest<M: Component>(
+    mut rotators: Query<(
+        Entity,
+        Ref<RotateToNearest<M>>,
+        &GlobalTransform,
+        &mut RotateTo,
+    )>,
+    targets: Query<(Entity, &GlobalTransform), With<M>>,
+    time: Option<Res<Time>>,
+    mut timer: ResMut<NearestTimer<M>>,
+) {
+    let interval = timer.interval;
+    // the first time, everything selects its target
+    let due = match timer.elapsed.as_mut() {
+        Some(elapsed) => {
+            *elapsed += time.as_deref().map_or(0.0, Time::delta_secs);
+            let due = *elapsed >= interval;
+            if due {
+                *elapsed = 0.0;
+            }
+            due
+        }
+        None => {
+            timer.elapsed = Some(0.0);
+            true
+        }
+    };
+    for (rotator, nearest, rotator_gt, mut rotate_to) in rotators.iter_mut() {
+        if !due && !nearest.is_added() {
+            continue;
+        }
+        let position = rotator_gt.translation();
+        let target = targets
+            .iter()
+            .filter(|(target, _)| *target != rotator)
+            .map(|(target, target_gt)| (target, target_gt.translation().distance_squared(position)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(Entity::PLACEHOLDER, |(target, _)| target);
+        let target = RotateTarget::Entity(target);
+        if rotate_to.target != target {
+            rotate_to.target = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::prelude::*;
+
+    use super::*;
+    use crate::RotateTowardsPlugin;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    fn spawn_rotator(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateToNearest::<Enemy>::default(),
+            ))
+            .id()
+    }
+
+    fn spawn_enemy(app: &mut App, position: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((GlobalTransform::from_translation(position), Enemy))
+            .id()
+    }
+
+    fn target_of(app: &App, rotator: Entity) -> RotateTarget {
+        app.world().get::<RotateTo>(rotator).unwrap().target
+    }
+
+    #[test]
+    fn nearest_is_selected_once_due() {
+        let mut app = App::new();
+        app.add_plugins((
+            RotateTowardsPlugin::new(false),
+            RotateToNearestPlugin::<Enemy>::default().with_interval(0.25),
+        ));
+        let mut time = Time::<()>::default();
+        time.advance_by(core::time::Duration::from_millis(100));
+        app.insert_resource(time);
+
+        let first = spawn_rotator(&mut app);
+        app.update();
+        assert_eq!(target_of(&app, first), RotateTarget::default());
+
+        // a new rotator selects its target right away, the others once the interval passed
+        let far = spawn_enemy(&mut app, Vec3::new(10.0, 0.0, 0.0));
+        let second = spawn_rotator(&mut app);
+        app.update();
+        assert_eq!(target_of(&app, first), RotateTarget::default());
+        assert_eq!(target_of(&app, second), RotateTarget::Entity(far));
+
+        let near = spawn_enemy(&mut app, Vec3::new(1.0, 0.0, 0.0));
+        app.update();
+        assert_eq!(target_of(&app, second), RotateTarget::Entity(far));
+        app.update();
+        for rotator in [first, second] {
+            assert_eq!(target_of(&app, rotator), RotateTarget::Entity(near));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    component::Mutable,
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
+use bevy_math::prelude::*;
+
+use crate::{components::RotateLocalRotation, plugin::RotateTowardsSystems};
+
+/// Plugin that copies the [`RotateLocalRotation`] of every entity with the component `T` into it,
+/// with `write`, after the rotation systems run, like into a rotation your animation blends with
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`RotationSinkPlugin::in_schedule`].
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// #[derive(Component)]
+/// struct AimRotation(Quat);
+///
+/// App::new().add_plugins((
+///     RotateTowardsPlugin::default(),
+///     RotationSinkPlugin::new(|aim: &mut AimRotation, rotation| aim.0 = rotation),
+/// ));
+///
+/// fn spawn_aim(mut commands: Commands, target: Entity) {
+///     commands.spawn((
+///         RotateTo::new(target),
+///         RotateLocalRotation::default(),
+///         AimRotation(Quat::IDENTITY),
+///     ));
+/// }
+/// ```
+pub struct RotationSinkPlugin<T: Component<Mutability = Mutable>> {
+    /// the schedule the rotations are copied in
+    schedule: InternedScheduleLabel,
+    /// writes the local rotation into the component
+    write: fn(&mut T, Quat),
+}
+
+impl<T: Component<Mutability = Mutable>> RotationSinkPlugin<T> {
+    /// Copies the local rotation into `T` with `write`
+    pub fn new(write: fn(&mut T, Quat)) -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            write,
+        }
+    }
+
+    /// Copies the rotations in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<T: Component<Mutability = Mutable>> Plugin for RotationSinkPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let write = self.write;
+        app.add_systems(
+            self.schedule,
+            (move |outputs: Query<
+                (&RotateLocalRotation, &mut T),
+                Changed<RotateLocalRotation>,
+            >| {
+                sync_local_rotation(outputs, write);
+            })
+            .after(RotateTowardsSystems::Rotate),
+        );
+    }
+}
+
+/// Copies every [`RotateLocalRotation`] into its `T`, once there is one
+fn sync_local_rotation<T: Component<Mutability = Mutable>>(
+    mut outputs: Query<(&RotateLocalRotation, &mut T), Changed<RotateLocalRotation>>,
+    write: fn(&mut T, Quat),
+) {
+    for (local_rotation, mut output) in outputs.iter_mut() {
+        if let Some(rotation) = local_rotation.0 {
+            write(&mut output, rotation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use bevy_time::prelude::*;
+    use bevy_transform::prelude::*;
+
+    use super::*;
+    use crate::{
+        components::{RotateSpeedLimit, RotateTo},
+        RotateTowardsPlugin,
+    };
+
+    #[derive(Component, Default)]
+    struct AimRotation(Quat);
+
+    #[test]
+    fn local_rotation_is_copied_into_the_sink() {
+        let mut app = App::new();
+        app.add_plugins((
+            RotateTowardsPlugin::new(false),
+            RotationSinkPlugin::new(|aim: &mut AimRotation, rotation| aim.0 = rotation),
+        ));
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        app.insert_resource(time);
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::X),
+                RotateSpeedLimit {
+                    max_degrees_per_second: 45.0,
+                },
+                RotateLocalRotation::default(),
+                AimRotation::default(),
+            ))
+            .id();
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().get::<Transform>(rotator).unwrap().rotation,
+            Quat::IDENTITY
+        );
+        let aim = app.world().get::<AimRotation>(rotator).unwrap().0;
+        let local_rotation = app.world().get::<RotateLocalRotation>(rotator).unwrap();
+        assert_eq!(local_rotation.0, Some(aim));
+        // turned on from the kept rotation every frame
+        let degrees = aim.angle_between(Quat::IDENTITY).to_degrees();
+        assert!((degrees - 13.5).abs() < 0.01, "{degrees}");
+    }
+}
@@ -0,0 +1,524 @@
+use bevy_app::{prelude::*, AnimationSystems};
+use bevy_ecs::{
+    prelude::*,
+    schedule::{BoxedCondition, InternedScheduleLabel, InternedSystemSet, ScheduleLabel},
+};
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+#[cfg(feature = "bevy_camera")]
+use crate::camera::{retarget_look_at_camera, AlwaysRotate, LookAtCamera, SkipInvisibleRotators};
+#[cfg(feature = "bevy_scene")]
+use crate::scene::validate_scene_targets;
+use crate::{
+    acquisition::{
+        acquire_targets, build_targetable_grid, retarget_rotate_to_any, AutoTarget, RotateToAny,
+        TargetSelection, Targetable, TargetableGrid,
+    },
+    components::*,
+    line_of_sight::{LineOfSight, LineOfSightMemory, LineOfSightState},
+    lod::{LodBand, RotateLod, RotateLodBands},
+    look_chain::{turn_look_chains, LookChain, LookChainLink, LookChainState},
+    named::{
+        resolve_named_targets, resolve_path_targets, resolve_persistent_targets, PersistentId,
+        PersistentTargets, RotateToNamed, RotateToPath, RotateToPersistent,
+    },
+    presets::{follow_billboard_anchors, HealthBarBillboard, TextBillboard},
+    surface_normal::{SurfaceNormal, SurfaceNormalState},
+    systems::{
+        any_rotators_exist, measure_rotators, output_rotations, return_to_rest,
+        rotate_towards_with_updated_global_transforms,
+        rotate_towards_without_updating_global_transforms, stop_untargeted_rotators,
+        sync_rotating_to, unsettle_changed_rotators, warn_rotate_cycles, BrokenHierarchies,
+        LostTargets,
+    },
+    turret::{aim_turrets, TurretAim},
+    velocity::{estimate_velocities, EstimateVelocity, EstimateVelocityState},
+};
+
+/// Plugin that constantly rotates entities towards a selected target when they have the [`RotateTo`]
+/// or [`RotateToDirection`] component on them.
+///
+/// If you only want the math for calculating the local rotation needed to look at a target,
+/// see the functions in the [`math`](crate::math) module, like
+/// [`calculate_local_rotation_to_target`](crate::calculate_local_rotation_to_target)
+/// or [`calculate_rotation_to_target_2d`](crate::calculate_rotation_to_target_2d) in 2D
+///
+/// By default the rotation runs in [`PostUpdate`], before the transforms are propagated,
+/// see [`RotateTowardsPlugin::in_schedule`] for running it in other schedules, like [`FixedPostUpdate`],
+/// and [`RotateTowardsPlugin::after`] and [`RotateTowardsPlugin::before`] for ordering it
+///
+/// The rotators are rotated in parallel on the compute task pool. The global transforms of their
+/// targets and parents are looked up once before that, so the parallel part only reads them.
+/// `cargo bench --bench rotate` compares a single thread to all of the cores available. On a
+/// single core Intel Xeon VM, where both are one thread, a frame with 50 000 rotators facing a
+/// moving target takes about 18ms, and 25ms as children of a parent, with updated global
+/// transforms.
+///
+/// A rotator that faces its target does not solve its rotation again until it, its parent, or its
+/// target moves, something else rotates it, or a component changing where it faces, like its
+/// [`RotateOffset`], changes. It is still visited every frame to check the change ticks of those,
+/// but returns before resolving its target or the transforms of its parents. In the `rotate`
+/// benchmark, on a single core Intel Xeon VM, a frame with 50 000 rotators takes about 6ms while
+/// nothing moves, and about 18ms while their target moves.
+///
+/// Rotators within the hierarchy of other rotators, like a camera on a tracking gimbal, are
+/// rotated after the rotators above them, so the whole chain faces its targets in the same frame.
+///
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// # struct MyAimSet;
+/// App::new().add_plugins(
+///     RotateTowardsPlugin::default()
+///         .in_schedule(FixedPostUpdate)
+///         .after(MyAimSet),
+/// );
+/// ```
+pub struct RotateTowardsPlugin {
+    /// determines if the plugins shall
+    /// calculate new global transforms before trying to change rotation to match the target
+    /// This can have a negative effect on performance, but helps combat the rotation lagging behind,
+    /// rotators with [`UsePropagatedTransforms`] do without it
+    calculate_new_globals: bool,
+    /// the schedule the rotation systems run in
+    schedule: InternedScheduleLabel,
+    /// sets the rotation systems run after
+    after: Vec<InternedSystemSet>,
+    /// sets the rotation systems run before
+    before: Vec<InternedSystemSet>,
+    /// the default for rotators without their own [`MissingTargetPolicy`]
+    missing_target_policy: MissingTargetPolicy,
+    /// the default for rotators without their own [`RotateEpsilon`]
+    epsilon: RotateEpsilon,
+    /// run conditions of all the systems
+    conditions: Vec<ConditionFactory>,
+    /// rotates only some of the rotators every time
+    time_slicing: Option<RotateTimeSlicing>,
+    /// publishes the rotations of all the rotators instead of setting them
+    compute_only: bool,
+    /// skips the rotators that are not visible
+    #[cfg(feature = "bevy_camera")]
+    skip_invisible: bool,
+}
+
+/// System sets of the [`RotateTowardsPlugin`], in the schedule selected for it
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RotateTowardsSystems {
+    /// Prepares the entities before they are rotated, like selecting their targets,
+    /// runs before [`RotateTowardsSystems::Rotate`]
+    Prepare,
+    /// Selects the targets of the entities, like the ones of [`AutoTarget`] or [`RotateToAny`],
+    /// runs within [`RotateTowardsSystems::Prepare`], before [`RotatingTo`] is synced
+    Retarget,
+    /// Rotates the entities towards their targets, then outputs and measures their rotations
+    Rotate,
+}
+
+/// Makes a new instance of a run condition, for every set it is added to
+type ConditionFactory = Box<dyn Fn() -> BoxedCondition + Send + Sync>;
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+/// Pauses all the systems of the [`RotateTowardsPlugin`] while `false`, like during a pause menu
+/// or a cutscene, without touching the rotators
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn open_pause_menu(mut enabled: ResMut<RotateTowardsEnabled>) {
+///     enabled.0 = false;
+/// }
+/// ```
+pub struct RotateTowardsEnabled(pub bool);
+
+impl Default for RotateTowardsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+/// Only rotates one in every `slices` of the rotators every time the rotation systems run,
+/// taking turns, for crowds too large to rotate all of them every frame, see
+/// [`RotateTowardsPlugin::time_sliced`]
+///
+/// Every rotator takes the same turn every time, it is rotated once every `slices` runs. The time
+/// since it was rotated last, that smoothing and speed limits use, is estimated as `slices`
+/// times the current delta, so the rotation stays as fast while the frame rate is stable.
+pub struct RotateTimeSlicing {
+    /// how many turns it takes to rotate all the rotators
+    pub slices: u32,
+}
+
+impl Default for RotateTimeSlicing {
+    fn default() -> Self {
+        Self { slices: 1 }
+    }
+}
+
+/// Run condition that is true unless the [`RotateTowardsEnabled`] resource pauses the rotation
+fn rotate_towards_enabled(enabled: Option<Res<RotateTowardsEnabled>>) -> bool {
+    enabled.is_none_or(|enabled| enabled.0)
+}
+
+impl Default for RotateTowardsPlugin {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl RotateTowardsPlugin {
+    pub fn new(calculate_new_globals: bool) -> Self {
+        Self {
+            calculate_new_globals,
+            schedule: PostUpdate.intern(),
+            after: Vec::new(),
+            before: Vec::new(),
+            missing_target_policy: MissingTargetPolicy::default(),
+            epsilon: RotateEpsilon::default(),
+            conditions: Vec::new(),
+            time_slicing: None,
+            compute_only: false,
+            #[cfg(feature = "bevy_camera")]
+            skip_invisible: false,
+        }
+    }
+
+    /// Runs the rotation systems in the given schedule instead of [`PostUpdate`]
+    ///
+    /// Use a fixed schedule, like [`FixedUpdate`] or [`FixedPostUpdate`], to have rotations, and
+    /// the aim state depending on them, be deterministic per tick. Smoothing and speed limits then
+    /// use the fixed timestep. Since transforms are only propagated in [`PostUpdate`],
+    /// keep calculating new global transforms when running in other schedules.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Runs the rotation systems after the given set, like a camera controller
+    /// that moves the targets, can be called multiple times
+    pub fn after(mut self, set: impl SystemSet) -> Self {
+        self.after.push(set.intern());
+        self
+    }
+
+    /// Runs the rotation systems after the animations are sampled, so a [`RotateAnimationLayer`]
+    /// blends onto this frame of the animations, instead of being overwritten by them
+    ///
+    /// The animations are sampled in [`PostUpdate`], where the rotation systems run by default.
+    pub fn after_animation(self) -> Self {
+        self.after(AnimationSystems)
+    }
+
+    /// Runs the rotation systems before the given set, like a physics write-back
+    /// that depends on the rotations, can be called multiple times
+    ///
+    /// The rotation systems always run before [`TransformSystems::Propagate`]
+    pub fn before(mut self, set: impl SystemSet) -> Self {
+        self.before.push(set.intern());
+        self
+    }
+
+    /// Only runs the systems while `condition` is true, like `in_state(GameState::Playing)`,
+    /// so the rotation stops during loading screens and menus, can be called multiple times
+    ///
+    /// ```no_run
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_mod_lookat::prelude::*;
+    /// #[derive(Resource)]
+    /// struct Loading;
+    ///
+    /// App::new().add_plugins(
+    ///     RotateTowardsPlugin::default().run_if(not(resource_exists::<Loading>)),
+    /// );
+    /// ```
+    pub fn run_if<M>(
+        mut self,
+        condition: impl SystemCondition<M> + Clone + Send + Sync + 'static,
+    ) -> Self {
+        self.conditions.push(Box::new(move || {
+            Box::new(IntoSystem::into_system(condition.clone()))
+        }));
+        self
+    }
+
+    /// Rotates one in every `slices` of the rotators every frame, trading how fast they react to
+    /// their targets for CPU time, see [`RotateTimeSlicing`]
+    pub fn time_sliced(mut self, slices: u32) -> Self {
+        self.time_slicing = Some(RotateTimeSlicing { slices });
+        self
+    }
+
+    /// Never sets the rotation of the [`Transform`] of a rotator, every rotator publishes its new
+    /// rotation in a [`DesiredLookRotation`] instead, for when another controller owns the transforms
+    pub fn compute_only(mut self) -> Self {
+        self.compute_only = true;
+        self
+    }
+
+    /// Skips rotating the rotators that are not visible, see [`SkipInvisibleRotators`]
+    #[cfg(feature = "bevy_camera")]
+    pub fn skip_invisible(mut self) -> Self {
+        self.skip_invisible = true;
+        self
+    }
+
+    /// Sets what happens when a targeted entity is missing, for rotators without their own
+    /// [`MissingTargetPolicy`] component, defaults to [`MissingTargetPolicy::WarnOnce`]
+    pub fn missing_target_policy(mut self, policy: MissingTargetPolicy) -> Self {
+        self.missing_target_policy = policy;
+        self
+    }
+
+    /// Sets how far apart positions, directions and rotations can be while they count as the
+    /// same, for rotators without their own [`RotateEpsilon`] component
+    pub fn epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = RotateEpsilon(epsilon);
+        self
+    }
+}
+
+impl Plugin for RotateTowardsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RotateTo>()
+            .register_type::<RotateTarget>()
+            .register_type::<RotateToDirection>()
+            .register_type::<RotateToCentroid>()
+            .register_type::<CentroidTarget>()
+            .register_type::<RotateSpeedLimit>()
+            .register_type::<RotateSmoothing>()
+            .register_type::<RotateSmoothingState>()
+            .register_type::<MissingTargetPolicy>()
+            .register_type::<AlignmentThreshold>()
+            .register_type::<AlignmentState>()
+            .register_type::<AngleToTarget>()
+            .register_type::<Rotate2D>()
+            .register_type::<ScreenAlignedBillboard>()
+            .register_type::<CylindricalBillboard>()
+            .register_type::<RotateAway>()
+            .register_type::<CoLocatedTarget>()
+            .register_type::<RotateReturnToRest>()
+            .register_type::<RotateReturnToRestState>()
+            .register_type::<PolePolicy>()
+            .register_type::<RotateEpsilon>()
+            .register_type::<RotateDeadband>()
+            .register_type::<UsePropagatedTransforms>()
+            .register_type::<ForwardAxis>()
+            .register_type::<RotateOffset>()
+            .register_type::<RotatePivot>()
+            .register_type::<RotateWeight>()
+            .register_type::<RotateWeightState>()
+            .register_type::<RotateAnimationLayer>()
+            .register_type::<RotateAnimationLayerState>()
+            .register_type::<RotateAxisWeights>()
+            .register_type::<RotateBanking>()
+            .register_type::<RotateBankingState>()
+            .register_type::<RotateSaccades>()
+            .register_type::<RotateSaccadesState>()
+            .register_type::<RotateIdleWander>()
+            .register_type::<RotateIdleWanderState>()
+            .register_type::<AxisLock>()
+            .register_type::<RotateConeLimit>()
+            .register_type::<AxisLockState>()
+            .register_type::<RotateConeLimitState>()
+            .register_type::<RotateJointLimits>()
+            .register_type::<AngleRange>()
+            .register_type::<TargetVelocity>()
+            .register_type::<EstimateVelocity>()
+            .register_type::<EstimateVelocityState>()
+            .register_type::<AimLead>()
+            .register_type::<RotateAngularVelocity>()
+            .register_type::<RotateLocalRotation>()
+            .register_type::<DesiredLookRotation>()
+            .register_type::<RotateTorque>()
+            .register_type::<RotateTorqueState>()
+            .register_type::<EngagementRange>()
+            .register_type::<OutsideRange>()
+            .register_type::<EngagementRangeState>()
+            .register_type::<FieldOfView>()
+            .register_type::<FieldOfViewReference>()
+            .register_type::<FieldOfViewState>()
+            .register_type::<Targetable>()
+            .register_type::<AutoTarget>()
+            .register_type::<TargetSelection>()
+            .register_type::<RotateToAny>()
+            .register_type::<RotateToNamed>()
+            .register_type::<RotateToPath>()
+            .register_type::<PersistentId>()
+            .register_type::<RotateToPersistent>()
+            .register_type::<PersistentTargets>()
+            .register_type::<TurretAim>()
+            .register_type::<LookChain>()
+            .register_type::<LookChainLink>()
+            .register_type::<LookChainState>()
+            .register_type::<LineOfSight>()
+            .register_type::<LineOfSightState>()
+            .register_type::<LineOfSightMemory>()
+            .register_type::<SurfaceNormal>()
+            .register_type::<SurfaceNormalState>()
+            .register_type::<TextBillboard>()
+            .register_type::<HealthBarBillboard>()
+            .register_type::<RotatingTo>()
+            .register_type::<RotatedToBy>()
+            .register_type::<UpDirection>()
+            .register_type::<RotateTowardsEnabled>()
+            .register_type::<RotateTimeSlicing>()
+            .register_type::<RotateLod>()
+            .register_type::<RotateLodBands>()
+            .register_type::<LodBand>();
+        #[cfg(feature = "bevy-inspector-egui")]
+        app.register_type_data::<RotateTarget, bevy_inspector_egui::inspector_egui_impls::InspectorEguiImpl>();
+        #[cfg(feature = "bevy_camera")]
+        {
+            app.register_type::<LookAtCamera>()
+                .register_type::<SkipInvisibleRotators>()
+                .register_type::<AlwaysRotate>();
+            if self.skip_invisible {
+                app.init_resource::<SkipInvisibleRotators>();
+            }
+        }
+
+        app.insert_resource(self.missing_target_policy)
+            .insert_resource(self.epsilon)
+            .init_resource::<LostTargets>()
+            .init_resource::<BrokenHierarchies>()
+            .init_resource::<PersistentTargets>()
+            .init_resource::<RotateTowardsEnabled>();
+        #[cfg(feature = "bevy_scene")]
+        app.add_observer(validate_scene_targets);
+        if let Some(time_slicing) = self.time_slicing {
+            app.insert_resource(time_slicing);
+        }
+        if self.compute_only {
+            app.register_required_components::<RotateTo, DesiredLookRotation>()
+                .register_required_components::<RotateToDirection, DesiredLookRotation>()
+                .register_required_components::<RotateToCentroid, DesiredLookRotation>();
+        }
+        app.configure_sets(
+            self.schedule,
+            RotateTowardsSystems::Retarget.in_set(RotateTowardsSystems::Prepare),
+        )
+        .add_systems(
+            self.schedule,
+            // outside of the sets, so it is not paused along with them
+            sync_rotating_to
+                .after(RotateTowardsSystems::Retarget)
+                .before(RotateTowardsSystems::Rotate),
+        );
+        let mut prepare_set = RotateTowardsSystems::Prepare.before(RotateTowardsSystems::Rotate);
+        let mut rotate_set = RotateTowardsSystems::Rotate
+            .before(TransformSystems::Propagate)
+            .run_if(any_rotators_exist);
+        for &set in &self.after {
+            prepare_set = prepare_set.after(set);
+            rotate_set = rotate_set.after(set);
+        }
+        for &set in &self.before {
+            rotate_set = rotate_set.before(set);
+        }
+        let mut sets = (RotateTowardsSystems::Prepare, RotateTowardsSystems::Rotate)
+            .run_if(rotate_towards_enabled);
+        for condition in &self.conditions {
+            sets.run_if_dyn(condition());
+        }
+        app.configure_sets(self.schedule, (prepare_set, rotate_set))
+            .configure_sets(self.schedule, sets);
+
+        // the outputs and measurements of the rotators, once they are rotated
+        let outputs = (output_rotations, measure_rotators);
+        if self.calculate_new_globals {
+            app.add_systems(
+                self.schedule,
+                (
+                    unsettle_changed_rotators,
+                    rotate_towards_with_updated_global_transforms,
+                    outputs,
+                )
+                    .chain()
+                    .in_set(RotateTowardsSystems::Rotate),
+            );
+        } else {
+            app.add_systems(
+                self.schedule,
+                (
+                    unsettle_changed_rotators,
+                    rotate_towards_without_updating_global_transforms,
+                    outputs,
+                )
+                    .chain()
+                    .in_set(RotateTowardsSystems::Rotate),
+            );
+        }
+        app.add_systems(
+            self.schedule,
+            aim_turrets.in_set(RotateTowardsSystems::Rotate),
+        );
+        app.add_systems(
+            self.schedule,
+            warn_rotate_cycles.in_set(RotateTowardsSystems::Rotate),
+        );
+        app.add_systems(
+            self.schedule,
+            follow_billboard_anchors.in_set(RotateTowardsSystems::Prepare),
+        );
+        // before the rotators, so the rest rotation is known before they first rotate
+        app.add_systems(
+            self.schedule,
+            return_to_rest
+                .run_if(any_with_component::<RotateReturnToRest>)
+                .in_set(RotateTowardsSystems::Prepare),
+        );
+        // before the rotators, so the ones on a chain rotate from where it is turned to
+        app.add_systems(
+            self.schedule,
+            turn_look_chains
+                .run_if(any_with_component::<LookChain>)
+                .in_set(RotateTowardsSystems::Prepare),
+        );
+        app.add_systems(
+            self.schedule,
+            stop_untargeted_rotators
+                .run_if(
+                    any_with_component::<RotateAngularVelocity>
+                        .or(any_with_component::<RotateTorqueState>),
+                )
+                .in_set(RotateTowardsSystems::Prepare),
+        );
+        app.add_systems(
+            self.schedule,
+            retarget_rotate_to_any.in_set(RotateTowardsSystems::Retarget),
+        );
+        app.add_systems(
+            self.schedule,
+            estimate_velocities
+                .run_if(any_with_component::<EstimateVelocity>)
+                .in_set(RotateTowardsSystems::Prepare),
+        );
+        app.add_systems(
+            self.schedule,
+            (
+                resolve_named_targets.run_if(any_with_component::<RotateToNamed>),
+                resolve_path_targets.run_if(any_with_component::<RotateToPath>),
+                resolve_persistent_targets.run_if(any_with_component::<RotateToPersistent>),
+            )
+                .in_set(RotateTowardsSystems::Retarget),
+        );
+        app.init_resource::<TargetableGrid>().add_systems(
+            self.schedule,
+            (build_targetable_grid, acquire_targets)
+                .chain()
+                .run_if(any_with_component::<AutoTarget>)
+                .in_set(RotateTowardsSystems::Retarget),
+        );
+        #[cfg(feature = "bevy_camera")]
+        app.add_systems(
+            self.schedule,
+            retarget_look_at_camera.in_set(RotateTowardsSystems::Retarget),
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::components::{ForwardAxis, ScreenAlignedBillboard};
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(ScreenAlignedBillboard, ForwardAxis = ForwardAxis(Dir3::Z))]
+#[cfg_attr(feature = "bevy_camera", require(crate::camera::LookAtCamera))]
+/// Preset for world space text, like nametags, that keeps it facing the camera
+///
+/// Text is drawn facing `Vec3::Z`, so that is its [`ForwardAxis`], turning the front of the text
+/// towards the camera instead of the default forward direction, so it is never seen mirrored.
+/// Its up direction follows the up direction of the camera, see [`ScreenAlignedBillboard`].
+///
+/// With the `bevy_camera` feature the active camera is targeted through
+/// `LookAtCamera`, otherwise add a [`RotateTo`](crate::RotateTo) targeting it.
+pub struct TextBillboard;
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(ScreenAlignedBillboard, ForwardAxis = ForwardAxis(Dir3::Z))]
+#[cfg_attr(feature = "bevy_camera", require(crate::camera::LookAtCamera))]
+/// Preset for world space health bars, and other quads floating over an entity, that keeps the
+/// entity at an offset from the entity it is anchored to, facing the camera
+///
+/// Like [`TextBillboard`], the front of the quad is expected to face `Vec3::Z`, its [`ForwardAxis`],
+/// as it does for a `Rectangle` mesh. The position is updated before the rotation, in the same schedule.
+///
+/// With the `bevy_camera` feature the active camera is targeted through
+/// `LookAtCamera`, otherwise add a [`RotateTo`](crate::RotateTo) targeting it.
+pub struct HealthBarBillboard {
+    /// the entity to float over, like the unit the health bar belongs to
+    #[entities]
+    pub anchor: Entity,
+    /// world space offset from the anchor, so the bar stays above it when it turns
+    pub offset: Vec3,
+}
+
+/// Floats one unit over [`Entity::PLACEHOLDER`], set the anchor before use
+impl Default for HealthBarBillboard {
+    fn default() -> Self {
+        Self::new(Entity::PLACEHOLDER, Vec3::Y)
+    }
+}
+
+impl HealthBarBillboard {
+    /// Floats over `anchor`, offset by `offset` in world space
+    pub fn new(anchor: Entity, offset: Vec3) -> Self {
+        Self { anchor, offset }
+    }
+}
+
+/// Moves every [`HealthBarBillboard`] to its offset from its anchor
+pub(crate) fn follow_billboard_anchors(
+    bars: Query<(Entity, &HealthBarBillboard, Option<&ChildOf>)>,
+    mut params: ParamSet<(TransformHelper, Query<&mut Transform>)>,
+    mut translations: Local<Vec<(Entity, Vec3)>>,
+) {
+    // the global transforms are computed first, since they depend on the transforms being written
+    let trans_helper = params.p0();
+    translations.extend(bars.iter().filter_map(|(entity, bar, child_of)| {
+        let anchor_gt = trans_helper.compute_global_transform(bar.anchor).ok()?;
+        let translation = anchor_gt.translation() + bar.offset;
+        let Some(child_of) = child_of else {
+            return Some((entity, translation));
+        };
+        let parent_gt = trans_helper
+            .compute_global_transform(child_of.parent())
+            .ok()?;
+        Some((
+            entity,
+            parent_gt.affine().inverse().transform_point3(translation),
+        ))
+    }));
+
+    let mut transforms = params.p1();
+    for (entity, translation) in translations.drain(..) {
+        // only once it moves, so the bars do not change every frame
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
+}
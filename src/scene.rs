@@ -0,0 +1,140 @@
+use bevy_ecs::prelude::*;
+use bevy_scene::{SceneInstanceReady, SceneSpawner};
+use bevy_transform::prelude::*;
+
+use crate::components::{report_missing_target, RotateTo};
+
+/// Reports the rotators of a scene instance that is ready, whose targeted entity still has no
+/// [`GlobalTransform`], like one outside of the scene, instead of when they are first rotated
+pub(crate) fn validate_scene_targets(
+    ready: On<SceneInstanceReady>,
+    spawner: Option<Res<SceneSpawner>>,
+    rotators: Query<&RotateTo>,
+    targets: Query<(), With<GlobalTransform>>,
+    mut commands: Commands,
+) {
+    let Some(spawner) = spawner else {
+        return;
+    };
+    for rotator in spawner.iter_instance_entities(ready.instance_id) {
+        let Some(target) = rotators
+            .get(rotator)
+            .ok()
+            .and_then(|rotate_to| rotate_to.target.entity())
+        else {
+            continue;
+        };
+        if !targets.contains(target) {
+            commands.queue(move |world: &mut World| {
+                report_missing_target(world.into(), rotator, target);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_asset::{AssetPlugin, Assets};
+    use bevy_ecs::entity::EntityHashMap;
+    use bevy_scene::{DynamicScene, DynamicSceneBuilder, ScenePlugin};
+
+    use super::*;
+    use crate::{events::TargetLost, RotateTarget, RotateTowardsEnabled, RotateTowardsPlugin};
+
+    /// The rotators whose target was lost
+    #[derive(Resource, Default)]
+    struct Lost(Vec<Entity>);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            AssetPlugin::default(),
+            ScenePlugin,
+            RotateTowardsPlugin::new(false),
+        ))
+        .register_type::<Transform>()
+        .init_resource::<Lost>()
+        .add_observer(|lost: On<TargetLost>, mut rotators: ResMut<Lost>| {
+            rotators.0.push(lost.entity);
+        });
+        // the ids of the scene are taken in the world it is spawned in
+        for _ in 0..5 {
+            app.world_mut().spawn_empty();
+        }
+        app
+    }
+
+    /// A scene of a target, a rotator targeting it, one targeting an entity outside of the scene
+    /// and one targeting an entity of the scene without a transform
+    fn scene() -> (DynamicScene, [Entity; 4]) {
+        let mut app = app();
+        let world = app.world_mut();
+        let target = world.spawn(Transform::from_xyz(0.0, 0.0, 10.0)).id();
+        let outside = world.spawn(Transform::default()).id();
+        let untransformed = world.spawn_empty().id();
+        let mut spawn_rotator = |target| {
+            world
+                .spawn((Transform::default(), RotateTo::new(target)))
+                .id()
+        };
+        let entities = [
+            target,
+            spawn_rotator(target),
+            spawn_rotator(outside),
+            spawn_rotator(untransformed),
+        ];
+        let scene = DynamicSceneBuilder::from_world(world)
+            .deny_all_components()
+            .allow_component::<Transform>()
+            .allow_component::<RotateTo>()
+            .extract_entities(entities.into_iter().chain([untransformed]))
+            .build();
+        (scene, entities)
+    }
+
+    #[test]
+    fn targets_are_mapped_before_they_are_validated() {
+        let (scene, [target, rotator, outside_rotator, _]) = scene();
+        let mut app = app();
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let mut entity_map = EntityHashMap::default();
+        scene
+            .write_to_world_with(app.world_mut(), &mut entity_map, &registry)
+            .unwrap();
+        let rotate_to = app.world().get::<RotateTo>(entity_map[&rotator]).unwrap();
+        assert_eq!(rotate_to.target, RotateTarget::Entity(entity_map[&target]));
+        // only the target outside of the scene is missing once inserted
+        let lost = &app.world().resource::<Lost>().0;
+        assert_eq!(lost, &[entity_map[&outside_rotator]]);
+    }
+
+    #[test]
+    fn targets_without_a_transform_are_reported_once_the_scene_is_ready() {
+        let (scene, _) = scene();
+        let mut app = app();
+        // the rotation systems do not report them
+        app.insert_resource(RotateTowardsEnabled(false));
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(scene);
+        let instance = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_dynamic(scene);
+        app.update();
+        assert!(app
+            .world()
+            .resource::<SceneSpawner>()
+            .instance_is_ready(instance));
+        // the one targeting outside of the scene, and the one targeting an entity without a transform
+        let lost = &app.world().resource::<Lost>().0;
+        assert_eq!(lost.len(), 2, "{lost:?}");
+        for &rotator in lost {
+            let rotate_to = app.world().get::<RotateTo>(rotator).unwrap();
+            let target = rotate_to.target.entity().unwrap();
+            assert!(app.world().get::<GlobalTransform>(target).is_none());
+        }
+    }
+}
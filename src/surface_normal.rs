@@ -0,0 +1,210 @@
+use core::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+    system::{StaticSystemParam, SystemParam, SystemParamItem},
+};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::plugin::RotateTowardsSystems;
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(SurfaceNormalState)]
+/// Samples the normal of the surface beneath the entity, with the [`SurfaceNormalBackend`] of a
+/// [`SurfaceNormalPlugin`], for [`UpDirection::SurfaceNormal`](crate::UpDirection::SurfaceNormal)
+///
+/// Useful for ground units that should face their targets while staying aligned to slopes.
+pub struct SurfaceNormal {
+    /// world space direction the ray is cast in, from the entity, to find the surface
+    #[cfg_attr(feature = "serde", serde(with = "crate::components::dir3_serde"))]
+    pub direction: Dir3,
+    /// how far away from the entity the surface is searched for
+    pub max_distance: f32,
+}
+
+impl Default for SurfaceNormal {
+    fn default() -> Self {
+        Self {
+            direction: Dir3::NEG_Y,
+            max_distance: 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The normal of the surface beneath a [`SurfaceNormal`], kept up to date by the
+/// [`SurfaceNormalPlugin`]
+pub struct SurfaceNormalState {
+    /// world space normal of the surface, `None` while there is no surface within reach
+    pub normal: Option<Dir3>,
+}
+
+/// Casts a ray and finds the normal of the surface it hits, like with the spatial queries of a
+/// physics engine, see [`SurfaceNormalPlugin`]
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SystemParamItem};
+/// # use bevy_math::prelude::*;
+/// # use bevy_app::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// /// A ground plane tilted around X
+/// #[derive(Resource)]
+/// struct Slope(Dir3);
+///
+/// impl SurfaceNormalBackend for Slope {
+///     type Param = Res<'static, Slope>;
+///
+///     fn surface_normal(
+///         slope: &SystemParamItem<Self::Param>,
+///         _origin: Vec3,
+///         _direction: Dir3,
+///         _max_distance: f32,
+///         _rotator: Entity,
+///     ) -> Option<Dir3> {
+///         Some(slope.0)
+///     }
+/// }
+///
+/// App::new().add_plugins((
+///     RotateTowardsPlugin::default(),
+///     SurfaceNormalPlugin::<Slope>::default(),
+/// ));
+/// ```
+pub trait SurfaceNormalBackend: Send + Sync + 'static {
+    /// what the backend needs to cast the ray, like the spatial query parameter of a physics engine
+    type Param: SystemParam;
+
+    /// The normal of the first surface the ray from `origin` along `direction` hits within
+    /// `max_distance`, leaving out the `rotator` itself, `None` if it hits nothing
+    fn surface_normal(
+        param: &SystemParamItem<Self::Param>,
+        origin: Vec3,
+        direction: Dir3,
+        max_distance: f32,
+        rotator: Entity,
+    ) -> Option<Dir3>;
+}
+
+/// Plugin that keeps the [`SurfaceNormalState`] of every [`SurfaceNormal`] up to date with the
+/// [`SurfaceNormalBackend`] `B`, before the rotation systems run
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`SurfaceNormalPlugin::in_schedule`].
+pub struct SurfaceNormalPlugin<B: SurfaceNormalBackend> {
+    /// the schedule the surface normals are sampled in
+    schedule: InternedScheduleLabel,
+    marker: PhantomData<fn() -> B>,
+}
+
+impl<B: SurfaceNormalBackend> Default for SurfaceNormalPlugin<B> {
+    fn default() -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<B: SurfaceNormalBackend> SurfaceNormalPlugin<B> {
+    /// Samples the surface normals in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<B: SurfaceNormalBackend> Plugin for SurfaceNormalPlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            update_surface_normals::<B>.in_set(RotateTowardsSystems::Prepare),
+        );
+    }
+}
+
+/// Samples the surface beneath every [`SurfaceNormal`]
+fn update_surface_normals<B: SurfaceNormalBackend>(
+    mut rotators: Query<(
+        Entity,
+        &SurfaceNormal,
+        &GlobalTransform,
+        &mut SurfaceNormalState,
+    )>,
+    param: StaticSystemParam<B::Param>,
+) {
+    for (rotator, surface, rotator_gt, mut state) in rotators.iter_mut() {
+        let normal = B::surface_normal(
+            &param,
+            rotator_gt.translation(),
+            surface.direction,
+            surface.max_distance,
+            rotator,
+        );
+        state.set_if_neq(SurfaceNormalState { normal });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RotateTo, RotateTowardsPlugin, UpDirection};
+
+    /// A ground plane tilted around Z, or no ground at all
+    #[derive(Resource)]
+    struct Slope(Option<Dir3>);
+
+    impl SurfaceNormalBackend for Slope {
+        type Param = Res<'static, Slope>;
+
+        fn surface_normal(
+            slope: &SystemParamItem<Self::Param>,
+            _origin: Vec3,
+            _direction: Dir3,
+            _max_distance: f32,
+            _rotator: Entity,
+        ) -> Option<Dir3> {
+            slope.0
+        }
+    }
+
+    fn up_of(app: &App, entity: Entity) -> Vec3 {
+        app.world().get::<Transform>(entity).unwrap().rotation * Vec3::Y
+    }
+
+    #[test]
+    fn up_follows_the_surface_normal() {
+        let mut app = App::new();
+        app.add_plugins((
+            RotateTowardsPlugin::new(false),
+            SurfaceNormalPlugin::<Slope>::default(),
+        ));
+        let normal = Quat::from_rotation_z(0.3) * Dir3::Y;
+        app.insert_resource(Slope(Some(normal)));
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::new(0.0, 0.0, -10.0)).with_up(UpDirection::SurfaceNormal),
+                SurfaceNormal::default(),
+            ))
+            .id();
+        app.update();
+        let up = up_of(&app, rotator);
+        assert!(up.abs_diff_eq(*normal, 1e-5), "{up}");
+
+        // off the ground, the up direction falls back to Vec3::Y
+        app.insert_resource(Slope(None));
+        app.update();
+        let up = up_of(&app, rotator);
+        assert!(up.abs_diff_eq(Vec3::Y, 1e-5), "{up}");
+    }
+}
@@ -0,0 +1,3180 @@
+use core::{
+    f32::consts::{PI, TAU},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use bevy_ecs::{
+    archetype::{Archetype, ArchetypeId},
+    change_detection::Tick,
+    entity::{EntityHashMap, EntityHashSet},
+    lifecycle::HookContext,
+    prelude::*,
+    query::QueryData,
+    system::SystemParam,
+    world::DeferredWorld,
+};
+use bevy_log::warn;
+use bevy_math::prelude::*;
+use bevy_time::prelude::*;
+use bevy_transform::prelude::*;
+
+#[cfg(feature = "bevy_camera")]
+use crate::camera::{is_invisible, RotatorVisibility, SkipInvisibleRotators};
+use crate::{
+    components::*,
+    events::*,
+    line_of_sight::{LineOfSight, LineOfSightMemory, LineOfSightState},
+    lod::{lod_interval, RotateLod, RotateLodBands},
+    math::{
+        calculate_angular_velocity, calculate_local_rotation_around_axis,
+        calculate_local_rotation_to_direction, calculate_local_rotation_to_point,
+        calculate_rotation_to_direction_2d, calculate_rotation_to_target_2d, direction_from_parent,
+        direction_in_parent,
+    },
+    plugin::RotateTimeSlicing,
+    surface_normal::SurfaceNormalState,
+    turret::TurretAim,
+};
+
+pub(crate) fn warn_missing_target(rotator: Entity, target: Entity) {
+    warn!(
+        "Entity {rotator} rotates towards entity {target}, which does not exist or has no GlobalTransform"
+    );
+}
+
+/// Rotators whose targeted entity is currently missing, see [`TargetLost`]
+#[derive(Resource, Default)]
+pub(crate) struct LostTargets(pub(crate) EntityHashSet);
+
+/// Rotators that are not rotated, since the global transform of them or their parent could not be
+/// found, like when an ancestor has no [`Transform`] or was despawned, warned about once
+#[derive(Resource, Default)]
+pub(crate) struct BrokenHierarchies(pub(crate) EntityHashSet);
+
+/// Warns once about every cycle of rotators rotating towards each other, like two entities with a
+/// [`RotateTo`] targeting each other, see [`RotateTo`] for how they are rotated
+pub(crate) fn warn_rotate_cycles(
+    retargeted: Query<Entity, Changed<RotateTo>>,
+    rotate_tos: Query<&RotateTo>,
+    mut warned: Local<EntityHashSet>,
+) {
+    for rotator in retargeted.iter() {
+        let mut cycle = vec![rotator];
+        let closed = loop {
+            let Some(target) = rotate_tos
+                .get(cycle[cycle.len() - 1])
+                .ok()
+                .and_then(|rotate_to| rotate_to.target.entity())
+            else {
+                break false;
+            };
+            if target == rotator {
+                break true;
+            }
+            // a cycle the rotator only leads into, it is found from the rotators within it
+            if cycle.contains(&target) {
+                break false;
+            }
+            cycle.push(target);
+        };
+        if !closed || cycle.iter().all(|entity| warned.contains(entity)) {
+            continue;
+        }
+        warned.extend(cycle.iter().copied());
+        // start from the lowest entity, so the cycle is named the same way from any of them
+        let lowest = cycle.iter().min().copied();
+        if let Some(first) = cycle.iter().position(|entity| Some(*entity) == lowest) {
+            cycle.rotate_left(first);
+        }
+        warn!(
+            "Entities {cycle:?} rotate towards each other in a cycle, each rotates from where the others were before any of them was rotated"
+        );
+    }
+}
+
+/// What a rotator was last rotated towards, so its look rotation is only calculated again once
+/// something it depends on changes, and a settled rotator is not rotated again, which makes static
+/// scenes nearly free
+#[derive(Component, Default)]
+#[component(on_remove = forget_rotator)]
+pub(crate) struct RotateCache {
+    /// the inputs and the look rotation facing the target with them
+    look: Option<(RotateInputs, Quat)>,
+    /// the rotation the rotator settled at, `None` while it is still turning
+    settled: Option<Quat>,
+    /// the archetype of the rotator, if it was left as it was last time, settled facing its
+    /// target, so it is left as it is again until something it depends on changes
+    still: Option<ArchetypeId>,
+}
+
+/// The components of a rotator that change how it rotates, besides its transforms and targets, a
+/// settled rotator is rotated again once one of them is changed or inserted, and once one is
+/// removed, which moves the rotator to another archetype, see [`RotateCache`]
+type RotatorConfigChanged = Or<(
+    Or<(
+        Changed<RotateTo>,
+        Changed<RotateToDirection>,
+        Changed<RotateToCentroid>,
+        Changed<ChildOf>,
+        Changed<SurfaceNormalState>,
+        Changed<TargetVelocity>,
+        Changed<RotateEpsilon>,
+        Changed<RotateLodBands>,
+        Changed<MissingTargetPolicy>,
+        Changed<LineOfSight>,
+        Changed<LineOfSightState>,
+        Changed<LineOfSightMemory>,
+        Changed<FieldOfView>,
+        Changed<EngagementRange>,
+        Changed<CoLocatedTarget>,
+    )>,
+    Or<(
+        Changed<Rotate2D>,
+        Changed<CylindricalBillboard>,
+        Changed<AimLead>,
+        Changed<ForwardAxis>,
+        Changed<RotateOffset>,
+        Changed<RotatePivot>,
+        Changed<PolePolicy>,
+        Changed<AxisLock>,
+        Changed<RotateConeLimit>,
+        Changed<RotateJointLimits>,
+        Changed<RotateAxisWeights>,
+        Changed<RotateWeight>,
+        Changed<RotateDeadband>,
+        Changed<RotateSmoothing>,
+        Changed<RotateSpeedLimit>,
+    )>,
+    Or<(Changed<RotateAnimationLayer>, Changed<RotateTorque>)>,
+)>;
+
+/// Rotates the settled rotators again once one of the components they rotate with changes, see
+/// [`RotatorConfigChanged`]
+pub(crate) fn unsettle_changed_rotators(
+    mut rotators: Query<&mut RotateCache, RotatorConfigChanged>,
+) {
+    for mut cache in rotators.iter_mut() {
+        cache.still = None;
+    }
+}
+
+/// Removes a rotator from the [`LostTargets`] and [`BrokenHierarchies`], once it is despawned
+fn forget_rotator(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    if let Some(mut lost) = world.get_resource_mut::<LostTargets>() {
+        lost.0.remove(&entity);
+    }
+    if let Some(mut broken) = world.get_resource_mut::<BrokenHierarchies>() {
+        broken.0.remove(&entity);
+    }
+}
+
+/// Everything the look rotation of a rotator depends on
+#[derive(Clone, Copy)]
+struct RotateInputs {
+    rotator_gt: GlobalTransform,
+    parent_gt: Option<GlobalTransform>,
+    target: ResolvedTarget,
+    updir: Dir3,
+    config: RotateConfig,
+}
+
+/// When the optional components that change the look rotation for the same target were last
+/// changed, `None` without them, so changing, inserting or removing one calculates it again
+///
+/// The components applied after the look rotation, like the limits, weights or smoothing, are
+/// applied every time the rotator is rotated, cached or not.
+#[derive(Clone, Copy, PartialEq)]
+struct RotateConfig {
+    offset: Option<Tick>,
+    forward_axis: Option<Tick>,
+    axis_lock: Option<Tick>,
+    rotate_2d: Option<Tick>,
+    pole: Option<Tick>,
+    pivot: Option<Tick>,
+    cylindrical: Option<Tick>,
+}
+
+impl RotateInputs {
+    /// If the inputs are the same, within `epsilon`
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        let gt_eq =
+            |a: &GlobalTransform, b: &GlobalTransform| a.affine().abs_diff_eq(b.affine(), epsilon);
+        let parent_eq = match (&self.parent_gt, &other.parent_gt) {
+            (Some(a), Some(b)) => gt_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        gt_eq(&self.rotator_gt, &other.rotator_gt)
+            && parent_eq
+            && self.target.abs_diff_eq(&other.target, epsilon)
+            && self.updir.abs_diff_eq(*other.updir, epsilon)
+            && self.config == other.config
+    }
+}
+
+impl RotateCache {
+    /// The look rotation calculated last time, if it was with these inputs, within `epsilon`
+    fn look(&self, inputs: &RotateInputs, epsilon: f32) -> Option<Quat> {
+        let (cached_inputs, look) = self.look.as_ref()?;
+        cached_inputs.abs_diff_eq(inputs, epsilon).then_some(*look)
+    }
+
+    /// If the rotator settled at `rotation` last time, and is still rotated by it, within `epsilon`
+    fn settled_at(&self, rotation: Quat, current: Quat, epsilon: f32) -> bool {
+        // a quaternion and its negation are the same rotation
+        let eq = |a: Quat, b: Quat| a.abs_diff_eq(b, epsilon) || a.abs_diff_eq(-b, epsilon);
+        self.settled
+            .is_some_and(|settled| eq(settled, rotation) && eq(settled, current))
+    }
+}
+
+/// How a rotator was turned the last time it was rotated, on the rotators with a component to
+/// output it to or measure it with, see [`output_rotations`] and [`measure_rotators`]
+#[derive(Component, Clone, Copy, Default, PartialEq)]
+pub(crate) struct RotateSolution {
+    /// `None` once the rotator stopped tracking its target, it keeps its rotation then
+    turn: Option<RotateTurn>,
+    /// how far the new rotation is from facing the target, `None` while it is not rotated
+    /// towards the target, like when it returns to rest
+    angle: Option<AngleToTarget>,
+    /// if the target is occluded, so the rotator is not aligned with it
+    occluded: bool,
+}
+
+/// A turn of a rotator, see [`RotateSolution`]
+#[derive(Clone, Copy, PartialEq)]
+struct RotateTurn {
+    /// the local rotation it turned from
+    current: Quat,
+    /// the local rotation it turned to
+    rotation: Quat,
+    /// the global transform of its parent, the local rotations turn into world space with
+    parent_gt: Option<GlobalTransform>,
+    /// the seconds the turn took
+    delta_secs: f32,
+}
+
+/// What the turn of a rotator is output to, see [`output_rotations`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotationOutputs {
+    solution: &'static RotateSolution,
+    desired_look: Option<&'static mut DesiredLookRotation>,
+    torque: Option<(&'static RotateTorque, &'static mut RotateTorqueState)>,
+    angular_velocity: Option<&'static mut RotateAngularVelocity>,
+    local_rotation: Option<&'static mut RotateLocalRotation>,
+}
+
+/// Outputs the turns of the rotators into their [`DesiredLookRotation`], and the first of their
+/// [`RotateTorque`], [`RotateAngularVelocity`] or [`RotateLocalRotation`], which leave their
+/// [`Transform`] alone
+pub(crate) fn output_rotations(mut rotators: Query<RotationOutputs, Changed<RotateSolution>>) {
+    rotators.par_iter_mut().for_each(|mut rotator| {
+        let Some(turn) = rotator.solution.turn else {
+            // it stops turning on its own
+            if let Some(angular_velocity) = &mut rotator.angular_velocity {
+                angular_velocity.set_if_neq(RotateAngularVelocity(Vec3::ZERO));
+            }
+            if let Some((_, state)) = &mut rotator.torque {
+                state.set_if_neq(RotateTorqueState::default());
+            }
+            return;
+        };
+        // leave the rotation to the physics engine, the local rotation turns into
+        // world space with the rotation of the parent, which is only decomposed for the outputs
+        // that need it
+        let parent_rotation = || {
+            turn.parent_gt
+                .map_or(Quat::IDENTITY, |parent_gt| parent_gt.rotation())
+        };
+        if let Some(desired_look) = &mut rotator.desired_look {
+            desired_look.set_if_neq(DesiredLookRotation {
+                local: turn.rotation,
+                world: parent_rotation() * turn.rotation,
+            });
+        }
+        if let Some((torque, state)) = &mut rotator.torque {
+            let parent_rotation = parent_rotation();
+            let rotation = parent_rotation * turn.current;
+            let desired = parent_rotation * turn.rotation;
+            state.torque = torque.torque(rotation, desired, state, turn.delta_secs);
+            return;
+        }
+        if let Some(angular_velocity) = &mut rotator.angular_velocity {
+            let velocity = calculate_angular_velocity(turn.current, turn.rotation, turn.delta_secs);
+            let velocity = direction_from_parent(velocity, turn.parent_gt.as_ref());
+            angular_velocity.set_if_neq(RotateAngularVelocity(velocity));
+            return;
+        }
+        if let Some(local_rotation) = &mut rotator.local_rotation {
+            local_rotation.set_if_neq(RotateLocalRotation(Some(turn.rotation)));
+        }
+    });
+}
+
+/// How the turn of a rotator is measured, see [`measure_rotators`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotationMeasurements {
+    entity: Entity,
+    solution: &'static RotateSolution,
+    angle_to_target: Option<&'static mut AngleToTarget>,
+    alignment: Option<(&'static AlignmentThreshold, &'static mut AlignmentState)>,
+}
+
+/// Keeps the [`AngleToTarget`] of the rotators up to date, and triggers [`Aligned`] and
+/// [`AlignmentLost`] as they cross their [`AlignmentThreshold`]
+pub(crate) fn measure_rotators(
+    mut commands: Commands,
+    mut rotators: Query<RotationMeasurements, Changed<RotateSolution>>,
+) {
+    for mut rotator in rotators.iter_mut() {
+        let Some(angle) = rotator.solution.angle else {
+            continue;
+        };
+        if let Some(angle_to_target) = &mut rotator.angle_to_target {
+            angle_to_target.set_if_neq(angle);
+        }
+        let Some((threshold, state)) = &mut rotator.alignment else {
+            continue;
+        };
+        let aligned = angle.degrees <= threshold.degrees && !rotator.solution.occluded;
+        if aligned != state.aligned {
+            state.aligned = aligned;
+            let entity = rotator.entity;
+            if aligned {
+                commands.trigger(Aligned { entity });
+            } else if threshold.trigger_lost {
+                commands.trigger(AlignmentLost { entity });
+            }
+        }
+    }
+}
+
+/// Run condition that is true when any entity has a [`RotateTo`], [`RotateToDirection`],
+/// [`RotateToCentroid`] or [`TurretAim`], the [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) uses it to skip its
+/// systems in scenes without rotators
+pub fn any_rotators_exist(
+    rotators: Query<(), WithRotateTargets>,
+    turrets: Query<(), With<TurretAim>>,
+) -> bool {
+    !rotators.is_empty() || !turrets.is_empty()
+}
+
+/// Keeps [`RotatingTo`] matching the entity targeted by a [`RotateTo`] whose target was changed in
+/// place, the hooks of [`RotateTo`] keep it matching when it is inserted, replaced or removed
+pub(crate) fn sync_rotating_to(
+    mut commands: Commands,
+    changed: Query<(Entity, &RotateTo, Option<&RotatingTo>), Changed<RotateTo>>,
+) {
+    for (entity, rotate_to, rotating_to) in changed.iter() {
+        match (rotate_to.target.entity(), rotating_to) {
+            (Some(target), Some(rotating_to)) if rotating_to.0 == target => {}
+            (Some(target), _) => {
+                commands.entity(entity).try_insert(RotatingTo(target));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).try_remove::<RotatingTo>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Turns every [`RotateReturnToRest`] that does not rotate towards a target back to its rest rotation
+pub(crate) fn return_to_rest(
+    mut rotators: Query<(
+        &RotateReturnToRest,
+        &mut RotateReturnToRestState,
+        &mut Transform,
+        Option<RotateTargets>,
+    )>,
+    time: Option<Res<Time>>,
+) {
+    // without time, it turns back right away
+    let delta_secs = time.as_deref().map_or(f32::INFINITY, Time::delta_secs);
+    for (return_to_rest, mut state, mut transform, targets) in rotators.iter_mut() {
+        let state = &mut *state;
+        let rest = *state
+            .rest
+            .get_or_insert(return_to_rest.rest.unwrap_or(transform.rotation));
+        // the other targeting components are only rotated towards without a RotateTo
+        let tracking = match targets {
+            Some((Some(rotate_to), ..)) => rotate_to.enabled,
+            Some(_) => true,
+            None => false,
+        };
+        if tracking {
+            state.transition_start = None;
+            continue;
+        }
+        let start = match state.transition_start {
+            Some(start) => start,
+            None => {
+                state.transition_elapsed = 0.0;
+                *state.transition_start.insert(transform.rotation)
+            }
+        };
+        state.transition_elapsed += delta_secs;
+        let t = if return_to_rest.duration > 0.0 {
+            state.transition_elapsed / return_to_rest.duration
+        } else {
+            1.0
+        };
+        let rotation = start.slerp(rest, return_to_rest.ease.sample_clamped(t));
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+/// Stops the [`RotateAngularVelocity`] and the torque of the [`RotateTorqueState`] of every entity
+/// that stopped rotating towards a target, since the rotation systems no longer visit it
+pub(crate) fn stop_untargeted_rotators(
+    mut rotators: Query<
+        (
+            Option<&mut RotateAngularVelocity>,
+            Option<&mut RotateTorqueState>,
+        ),
+        WithoutRotateTargets,
+    >,
+) {
+    for (angular_velocity, torque) in rotators.iter_mut() {
+        if let Some(mut angular_velocity) = angular_velocity {
+            angular_velocity.set_if_neq(RotateAngularVelocity(Vec3::ZERO));
+        }
+        if let Some(mut torque) = torque {
+            torque.set_if_neq(RotateTorqueState::default());
+        }
+    }
+}
+
+/// How many times the rotation of a [`RotatePivot`] is refined
+const PIVOT_ITERATIONS: usize = 3;
+
+/// How far, in degrees, the target of an [`AxisLock`] can be past straight behind it, while it
+/// keeps turning the way it did
+const AXIS_LOCK_HYSTERESIS_DEGREES: f32 = 10.0;
+
+/// A target resolved into world space
+#[derive(Clone, Copy)]
+pub(crate) enum ResolvedTarget {
+    /// position to look at, and the up direction of the target, if it has one
+    Point(Vec3, Option<Dir3>),
+    /// direction to look along, and the up direction of the target, if it has one
+    Direction(Dir3, Option<Dir3>),
+}
+
+impl ResolvedTarget {
+    /// Resolves whichever of the targeting components the rotator has, along with its up direction
+    fn resolve_any(
+        rotate_to: Option<&RotateTo>,
+        rotate_to_direction: Option<&RotateToDirection>,
+        rotate_to_centroid: Option<&RotateToCentroid>,
+        screen_aligned: bool,
+        global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<(Self, UpDirection)> {
+        match (rotate_to, rotate_to_direction, rotate_to_centroid) {
+            (Some(rotate_to), ..) => Some((
+                Self::resolve(rotate_to, screen_aligned, global_transform)?,
+                rotate_to.updir,
+            )),
+            (None, Some(rotate_to_direction), _) => Some((
+                Self::Direction(rotate_to_direction.direction, None),
+                rotate_to_direction.updir,
+            )),
+            (None, None, Some(rotate_to_centroid)) => {
+                let centroid = rotate_to_centroid
+                    .centroid(|entity| Some(global_transform(entity)?.translation()))?;
+                Some((Self::Point(centroid, None), rotate_to_centroid.updir))
+            }
+            (None, None, None) => None,
+        }
+    }
+
+    fn resolve(
+        rotate_to: &RotateTo,
+        screen_aligned: bool,
+        global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<Self> {
+        match rotate_to.target {
+            RotateTarget::Entity(entity) => {
+                let target_gt = global_transform(entity)?;
+                if screen_aligned {
+                    // face the plane the target looks at, rather than its position
+                    Some(Self::Direction(target_gt.back(), Some(target_gt.up())))
+                } else {
+                    Some(Self::Point(
+                        rotate_to.aim_point(&target_gt),
+                        Some(target_gt.up()),
+                    ))
+                }
+            }
+            RotateTarget::Point(point) => Some(Self::Point(point, None)),
+            RotateTarget::Direction(direction) => Some(Self::Direction(direction, None)),
+        }
+    }
+
+    fn up(&self) -> Option<Dir3> {
+        match self {
+            Self::Point(_, up) | Self::Direction(_, up) => *up,
+        }
+    }
+
+    /// Flattens the target onto the plane perpendicular to `updir`, so looking at it only turns
+    /// the rotator around `updir`, `None` if the target is straight above or below the rotator
+    fn upright(self, rotator_gt: &GlobalTransform, updir: Dir3) -> Option<Self> {
+        let direction = self.direction_from(rotator_gt);
+        let direction = Dir3::new(direction.reject_from_normalized(*updir)).ok()?;
+        Some(Self::Direction(direction, self.up()))
+    }
+
+    /// The target mirrored through the rotator, so looking at it faces away from the target
+    fn mirrored(self, rotator_gt: &GlobalTransform) -> Self {
+        match self {
+            Self::Point(point, up) => Self::Point(2.0 * rotator_gt.translation() - point, up),
+            Self::Direction(direction, up) => Self::Direction(-direction, up),
+        }
+    }
+
+    /// If the target is a point at the position of the rotator, within `epsilon`, with no
+    /// direction to look along
+    fn co_located(&self, rotator_gt: &GlobalTransform, epsilon: f32) -> bool {
+        match self {
+            Self::Point(point, _) => {
+                point.distance_squared(rotator_gt.translation()) < epsilon * epsilon
+            }
+            Self::Direction(..) => false,
+        }
+    }
+
+    /// If the targets are the same, within `epsilon`
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        let up_eq = |a: &Option<Dir3>, b: &Option<Dir3>| match (a, b) {
+            (Some(a), Some(b)) => a.abs_diff_eq(**b, epsilon),
+            (None, None) => true,
+            _ => false,
+        };
+        match (self, other) {
+            (Self::Point(a, a_up), Self::Point(b, b_up)) => {
+                a.abs_diff_eq(*b, epsilon) && up_eq(a_up, b_up)
+            }
+            (Self::Direction(a, a_up), Self::Direction(b, b_up)) => {
+                a.abs_diff_eq(**b, epsilon) && up_eq(a_up, b_up)
+            }
+            _ => false,
+        }
+    }
+
+    /// The world space direction the rotator should look along
+    pub(crate) fn direction_from(&self, rotator_gt: &GlobalTransform) -> Vec3 {
+        match self {
+            Self::Point(point, _) => *point - rotator_gt.translation(),
+            Self::Direction(direction, _) => direction.as_vec3(),
+        }
+    }
+
+    fn local_rotation(
+        &self,
+        rotator_gt: &GlobalTransform,
+        parent_gt: Option<&GlobalTransform>,
+        updir: Dir3,
+    ) -> Quat {
+        match self {
+            Self::Point(point, _) => {
+                calculate_local_rotation_to_point(rotator_gt, *point, parent_gt, updir)
+            }
+            Self::Direction(direction, _) => {
+                calculate_local_rotation_to_direction(*direction, parent_gt, updir)
+            }
+        }
+    }
+
+    fn local_rotation_2d(
+        &self,
+        rotator_gt: &GlobalTransform,
+        parent_gt: Option<&GlobalTransform>,
+        rotate_2d: &Rotate2D,
+    ) -> Option<Quat> {
+        let angle_offset_degrees = rotate_2d.angle_offset_degrees;
+        match self {
+            Self::Point(point, _) => calculate_rotation_to_target_2d(
+                rotator_gt,
+                point.xy(),
+                parent_gt,
+                angle_offset_degrees,
+            ),
+            Self::Direction(direction, _) => {
+                calculate_rotation_to_direction_2d(direction.xy(), parent_gt, angle_offset_degrees)
+            }
+        }
+    }
+}
+
+impl AxisLockState {
+    /// The rotation to approach instead of `desired`, turning the way the rotator did while
+    /// `desired` is about half a turn around the `axis` away from `current`
+    fn hold_turn(&mut self, axis: Dir3, current: Quat, desired: Quat) -> Quat {
+        let delta = desired * current.inverse();
+        let mut angle = 2.0 * ops::atan2(delta.xyz().dot(*axis), delta.w);
+        // wrapped into -PI..=PI
+        if angle > PI {
+            angle -= TAU;
+        } else if angle < -PI {
+            angle += TAU;
+        }
+        let hysteresis = AXIS_LOCK_HYSTERESIS_DEGREES.to_radians();
+        if angle.abs() > PI - hysteresis && self.turning * angle < 0.0 {
+            // the long way round, halfway at a time, so approaching it turns the same way
+            let angle = angle + TAU * self.turning;
+            return Quat::from_axis_angle(*axis, angle / 2.0) * current;
+        }
+        if angle.abs() > f32::EPSILON {
+            self.turning = angle.signum();
+        }
+        desired
+    }
+}
+
+impl PolePolicy {
+    /// The target and up direction to rotate with, looking along a direction instead of at a
+    /// point while the fallback changes where the rotator looks
+    fn stabilize_target(
+        &self,
+        target: ResolvedTarget,
+        updir: Dir3,
+        rotator_gt: &GlobalTransform,
+    ) -> (ResolvedTarget, Dir3) {
+        let direction = target.direction_from(rotator_gt);
+        let (stable_direction, updir) = self.stabilize(direction, updir, rotator_gt);
+        if stable_direction == direction {
+            return (target, updir);
+        }
+        match Dir3::new(stable_direction) {
+            Ok(stable_direction) => (
+                ResolvedTarget::Direction(stable_direction, target.up()),
+                updir,
+            ),
+            Err(_) => (target, updir),
+        }
+    }
+}
+
+/// Selects the up direction for a rotator, `target_up` is `None` when the target has no
+/// orientation of its own, such as a [`RotateTarget::Point`]
+fn resolve_updir(
+    updir: UpDirection,
+    target_up: Option<Dir3>,
+    parent_gt: Option<&GlobalTransform>,
+    rotator_gt: &GlobalTransform,
+    surface_normal: Option<Dir3>,
+    velocity: Option<Vec3>,
+    global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+) -> Dir3 {
+    match updir {
+        // if the target has no up direction, fallback to bevy up direction
+        UpDirection::Target => target_up.unwrap_or(Dir3::Y),
+        UpDirection::Dir(dir) => dir,
+        UpDirection::Parent => {
+            if let Some(parent_gt) = parent_gt {
+                parent_gt.up()
+            } else {
+                // if there is no parent, fallback to bevy up direction
+                Dir3::Y
+            }
+        }
+        UpDirection::Entity(entity) => {
+            // if the entity is missing, fallback to bevy up direction
+            global_transform(entity).map_or(Dir3::Y, |gt| gt.up())
+        }
+        UpDirection::TowardEntity(entity) => global_transform(entity)
+            .and_then(|gt| Dir3::new(gt.translation() - rotator_gt.translation()).ok())
+            .unwrap_or(Dir3::Y),
+        UpDirection::AwayFromPoint(point) => {
+            Dir3::new(rotator_gt.translation() - point).unwrap_or(Dir3::Y)
+        }
+        // if there is no surface beneath, fallback to bevy up direction
+        UpDirection::SurfaceNormal => surface_normal.unwrap_or(Dir3::Y),
+        // if the rotator is not moving, fallback to bevy up direction
+        UpDirection::Velocity => velocity
+            .and_then(|velocity| Dir3::new(velocity).ok())
+            .unwrap_or(Dir3::Y),
+    }
+}
+
+/// How many rotators had their rotation set since the diagnostics were last measured, counted by
+/// the rotation systems while the `RotateTowardsDiagnosticsPlugin` is added
+#[derive(Resource, Default)]
+pub(crate) struct RotatedRotators(pub(crate) AtomicU32);
+
+/// Filters for entities with any of the components in [`RotateTargets`]
+pub(crate) type WithRotateTargets = Or<(
+    With<RotateTo>,
+    With<RotateToDirection>,
+    With<RotateToCentroid>,
+)>;
+
+/// Filters for entities without any of the components in [`RotateTargets`]
+pub(crate) type WithoutRotateTargets = (
+    Without<RotateTo>,
+    Without<RotateToDirection>,
+    Without<RotateToCentroid>,
+);
+
+/// Every component that selects something for a rotator to rotate towards
+pub(crate) type RotateTargets = AnyOf<(
+    Ref<'static, RotateTo>,
+    Ref<'static, RotateToDirection>,
+    Ref<'static, RotateToCentroid>,
+)>;
+
+/// A rotator, with the optional components that change how it rotates towards its target,
+/// grouped by the stage of the rotation they change
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct Rotator {
+    entity: Entity,
+    archetype: &'static Archetype,
+    child_of: Option<&'static ChildOf>,
+    targets: RotateTargets,
+    propagated: Has<UsePropagatedTransforms>,
+    surface_normal: Option<&'static SurfaceNormalState>,
+    epsilon: Option<&'static RotateEpsilon>,
+    lod_bands: Option<&'static RotateLodBands>,
+    cache: Option<&'static mut RotateCache>,
+    gates: RotateGates,
+    look: RotateLook,
+    modifiers: RotateModifiers,
+    motion: RotateMotion,
+    outputs: RotateOutputs,
+    #[cfg(feature = "bevy_camera")]
+    visibility: RotatorVisibility,
+}
+
+/// The optional components deciding if a rotator tracks its target
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotateGates {
+    missing_target_policy: Option<&'static MissingTargetPolicy>,
+    line_of_sight: Option<(&'static LineOfSight, &'static LineOfSightState)>,
+    line_of_sight_memory: Option<&'static LineOfSightMemory>,
+    field_of_view: Option<(&'static FieldOfView, &'static mut FieldOfViewState)>,
+    engagement_range: Option<(&'static EngagementRange, &'static mut EngagementRangeState)>,
+    co_located: Option<&'static CoLocatedTarget>,
+}
+
+/// The optional components that change the look rotation facing the target, which is cached
+/// while they and the inputs stay the same, see [`RotateConfig`]
+#[derive(QueryData)]
+pub(crate) struct RotateLook {
+    rotate_2d: Option<Ref<'static, Rotate2D>>,
+    screen_aligned: Has<ScreenAlignedBillboard>,
+    cylindrical: Option<Ref<'static, CylindricalBillboard>>,
+    away: Has<RotateAway>,
+    aim_lead: Option<&'static AimLead>,
+    forward_axis: Option<Ref<'static, ForwardAxis>>,
+    offset: Option<Ref<'static, RotateOffset>>,
+    pivot: Option<Ref<'static, RotatePivot>>,
+    pole: Option<Ref<'static, PolePolicy>>,
+    axis_lock: Option<Ref<'static, AxisLock>>,
+}
+
+/// The optional components applied to the look rotation, every time the rotator is rotated
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotateModifiers {
+    saccades: Option<(&'static RotateSaccades, &'static mut RotateSaccadesState)>,
+    cone_limit: Option<(&'static RotateConeLimit, &'static mut RotateConeLimitState)>,
+    joint_limits: Option<&'static RotateJointLimits>,
+    banking: Option<(&'static RotateBanking, &'static mut RotateBankingState)>,
+    axis_weights: Option<&'static RotateAxisWeights>,
+    weight: Option<(&'static RotateWeight, &'static mut RotateWeightState)>,
+    axis_lock: Option<&'static mut AxisLockState>,
+    deadband: Option<&'static RotateDeadband>,
+}
+
+/// The optional components changing how a rotator turns towards where it should face
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotateMotion {
+    smoothing: Option<(&'static RotateSmoothing, &'static mut RotateSmoothingState)>,
+    speed_limit: Option<&'static RotateSpeedLimit>,
+    animation_layer: Option<(
+        &'static RotateAnimationLayer,
+        &'static mut RotateAnimationLayerState,
+    )>,
+    idle_wander: Option<(
+        &'static RotateIdleWander,
+        &'static mut RotateIdleWanderState,
+    )>,
+}
+
+/// The optional components a rotator is rotated through, instead of its [`Transform`], and the
+/// [`RotateSolution`] they are output from, by [`output_rotations`]
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(crate) struct RotateOutputs {
+    local_rotation: Option<&'static RotateLocalRotation>,
+    desired_look: Has<DesiredLookRotation>,
+    angular_velocity: Has<RotateAngularVelocity>,
+    torque: Has<RotateTorque>,
+    solution: Option<&'static mut RotateSolution>,
+}
+
+impl RotateLookItem<'_, '_> {
+    /// When the optional components that change the look rotation were last changed, see
+    /// [`RotateConfig`]
+    fn config(&self) -> RotateConfig {
+        fn last_changed<T>(component: &Option<Ref<T>>) -> Option<Tick> {
+            component.as_ref().map(Ref::last_changed)
+        }
+        RotateConfig {
+            offset: last_changed(&self.offset),
+            forward_axis: last_changed(&self.forward_axis),
+            axis_lock: last_changed(&self.axis_lock),
+            rotate_2d: last_changed(&self.rotate_2d),
+            pole: last_changed(&self.pole),
+            pivot: last_changed(&self.pivot),
+            cylindrical: last_changed(&self.cylindrical),
+        }
+    }
+}
+
+impl RotateOutputsItem<'_, '_> {
+    /// If the rotator is rotated through its [`Transform`]
+    fn transform(&self) -> bool {
+        self.local_rotation.is_none()
+            && !self.desired_look
+            && !self.angular_velocity
+            && !self.torque
+    }
+
+    /// Keeps how the rotator turned, for its outputs and measurements, if it has any
+    fn solve(&mut self, solution: RotateSolution) {
+        if let Some(rotate_solution) = &mut self.solution {
+            **rotate_solution = solution;
+        }
+    }
+
+    /// Keeps how far the rotator is from facing its target once it turned, and if its target is
+    /// `occluded`, for its measurements, if it has any
+    fn measure(&mut self, angle: AngleToTarget, occluded: bool) {
+        if let Some(solution) = &mut self.solution {
+            solution.angle = Some(angle);
+            solution.occluded = occluded;
+        }
+    }
+}
+
+impl RotatorItem<'_, '_> {
+    /// If the target of the rotator was changed since the last time the systems ran
+    fn retargeted(&self) -> bool {
+        let (rotate_to, rotate_to_direction, rotate_to_centroid) = &self.targets;
+        rotate_to.as_ref().is_some_and(Ref::is_changed)
+            || rotate_to_direction.as_ref().is_some_and(Ref::is_changed)
+            || rotate_to_centroid.as_ref().is_some_and(Ref::is_changed)
+    }
+
+    /// The rotation the rotator turns from, the one of its [`RotateLocalRotation`] or
+    /// [`RotateAnimationLayer`] if it has one
+    fn current_rotation(&self, rotator_t: &Transform) -> Quat {
+        let layer = self
+            .motion
+            .animation_layer
+            .as_ref()
+            .and_then(|(_, state)| state.rotation);
+        self.outputs
+            .local_rotation
+            .and_then(|local_rotation| local_rotation.0)
+            .or(layer)
+            .unwrap_or(rotator_t.rotation)
+    }
+
+    /// Blends the `rotation` of a rotator with a [`RotateAnimationLayer`] onto its `animated`
+    /// rotation, or the one it had last time if it keeps its rotation
+    fn blend_layer(&mut self, animated: Quat, rotation: Option<Quat>) -> Option<Quat> {
+        let Some((layer, state)) = &mut self.motion.animation_layer else {
+            return rotation;
+        };
+        if rotation.is_some() {
+            state.rotation = rotation;
+        }
+        Some(animated.slerp(state.rotation?, layer.weight.clamp(0.0, 1.0)))
+    }
+
+    /// Stops what keeps turning the rotator on its own, its [`RotateAngularVelocity`] and the
+    /// torque of its [`RotateTorque`], once it stops tracking its target, it keeps its rotation
+    fn stop(&mut self) -> Option<Quat> {
+        // a rotator that stays stopped is not output again
+        if let Some(solution) = &mut self.outputs.solution {
+            solution.set_if_neq(RotateSolution::default());
+        }
+        None
+    }
+
+    /// Applies the optional smoothing and limits on how the rotator approaches the `desired` rotation
+    fn approach(&mut self, current: Quat, desired: Quat, delta_secs: Option<f32>) -> Quat {
+        let Some(delta_secs) = delta_secs else {
+            return desired;
+        };
+        let retargeted = self.retargeted();
+
+        let mut rotation = desired;
+        if let Some((smoothing, state)) = &mut self.motion.smoothing {
+            rotation = smoothing.smooth(current, rotation, state, delta_secs, retargeted);
+        }
+        if let Some(speed_limit) = self.motion.speed_limit {
+            rotation = speed_limit.limit(current, rotation, delta_secs);
+        }
+        rotation
+    }
+
+    /// The roll of the optional banking, for the `look` rotation facing the target
+    fn bank(&mut self, look: Quat, delta_secs: Option<f32>) -> Quat {
+        let (Some((banking, state)), Some(delta_secs)) = (&mut self.modifiers.banking, delta_secs)
+        else {
+            return Quat::IDENTITY;
+        };
+        banking.bank(look, state, delta_secs)
+    }
+}
+
+/// What the rotation systems share, besides how they look up global transforms
+#[derive(SystemParam)]
+pub(crate) struct RotateParams<'w, 's> {
+    commands: ParallelCommands<'w, 's>,
+    time: Option<Res<'w, Time>>,
+    missing_target_policy: Res<'w, MissingTargetPolicy>,
+    epsilon: Res<'w, RotateEpsilon>,
+    rotated: Option<Res<'w, RotatedRotators>>,
+    lost: Res<'w, LostTargets>,
+    broken: Res<'w, BrokenHierarchies>,
+    velocities: Query<'w, 's, Ref<'static, TargetVelocity>>,
+    time_slicing: Option<Res<'w, RotateTimeSlicing>>,
+    lod: Option<Res<'w, RotateLod>>,
+    #[cfg(feature = "bevy_camera")]
+    skip_invisible: Option<Res<'w, SkipInvisibleRotators>>,
+    /// how many times the system has run, to take turns with the time slicing
+    runs: Local<'s, u32>,
+    rotate_tos: Query<'w, 's, &'static RotateTo>,
+    /// the global transforms of the targeted entities, looked up once every time the system runs,
+    /// since many rotators often share a target, like the camera
+    target_transforms: Local<'s, EntityHashMap<GlobalTransform>>,
+    /// the targeted entities that may have moved since the system last ran
+    moved_targets: Local<'s, EntityHashSet>,
+    /// the global transforms of the parents of the rotators, and if they may have moved, looked up
+    /// once before the rotators are rotated in parallel, rather than by each of their children
+    parent_transforms: Local<'s, EntityHashMap<(Option<GlobalTransform>, bool)>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    /// the rotators within the hierarchy of another rotator, and the rotators above them, with how
+    /// many rotators are above them
+    chained: Local<'s, EntityHashMap<u32>>,
+    /// the global transforms of the chained rotators rotated so far, for the ones below them
+    chained_transforms: Local<'s, EntityHashMap<GlobalTransform>>,
+}
+
+impl RotateParams<'_, '_> {
+    /// Moves the time slicing on to the next turn, once every time the system runs
+    fn next_run(&mut self) {
+        *self.runs = self.runs.wrapping_add(1);
+    }
+
+    /// Finds the rotators within the hierarchy of another rotator, and the rotators above them,
+    /// which are rotated after the others, one level of the hierarchy after the other, see
+    /// [`RotateParams::chain_order`]
+    fn find_chains(
+        &mut self,
+        rotators: impl Iterator<Item = Entity>,
+        is_rotator: impl Fn(Entity) -> bool,
+    ) {
+        self.chained.clear();
+        self.chained_transforms.clear();
+        for rotator in rotators {
+            let depth = |entity| {
+                self.parents
+                    .iter_ancestors(entity)
+                    .filter(|ancestor| is_rotator(*ancestor))
+                    .count() as u32
+            };
+            let rotator_depth = depth(rotator);
+            if rotator_depth == 0 {
+                continue;
+            }
+            let ancestors = self
+                .parents
+                .iter_ancestors(rotator)
+                .filter(|ancestor| is_rotator(*ancestor))
+                .map(|ancestor| (ancestor, depth(ancestor)))
+                .collect::<Vec<_>>();
+            self.chained.insert(rotator, rotator_depth);
+            self.chained.extend(ancestors);
+        }
+    }
+
+    /// If the rotator is rotated after the others, within a chain of rotators
+    fn is_chained(&self, rotator: Entity) -> bool {
+        self.chained.contains_key(&rotator)
+    }
+
+    /// The chained rotators, ordered so every rotator is rotated after the rotators above it, and
+    /// otherwise by entity, so the order is the same every time
+    fn chain_order(&self) -> Vec<Entity> {
+        let mut order = self
+            .chained
+            .iter()
+            .map(|(entity, depth)| (*depth, *entity))
+            .collect::<Vec<_>>();
+        order.sort_unstable();
+        order.into_iter().map(|(_, entity)| entity).collect()
+    }
+
+    /// Remembers the new global transform of a chained rotator, given its new local
+    /// `transform`, so the rotators below it rotate from where it is rotated to
+    fn chained_rotated(
+        &mut self,
+        rotator: Entity,
+        transform: Transform,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+    ) {
+        let parent_gt = self.parents.get(rotator).ok().and_then(|child_of| {
+            self.chained_global_transform(child_of.parent(), &global_transform)
+        });
+        let rotator_gt = match parent_gt {
+            Some(parent_gt) => parent_gt.mul_transform(transform),
+            None => GlobalTransform::from(transform),
+        };
+        self.chained_transforms.insert(rotator, rotator_gt);
+    }
+
+    /// The global transform of an entity, moved along with the chained rotators above it that
+    /// were rotated already
+    fn chained_global_transform(
+        &self,
+        entity: Entity,
+        global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<GlobalTransform> {
+        if let Some(entity_gt) = self.chained_transforms.get(&entity) {
+            return Some(*entity_gt);
+        }
+        let Some((ancestor, ancestor_gt)) = self
+            .parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| Some((ancestor, *self.chained_transforms.get(&ancestor)?)))
+        else {
+            return global_transform(entity);
+        };
+        // the entity stays where it is relative to the rotated ancestor
+        let relative =
+            global_transform(ancestor)?.affine().inverse() * global_transform(entity)?.affine();
+        Some(GlobalTransform::from(ancestor_gt.affine() * relative))
+    }
+
+    /// Looks up the global transforms of the targeted entities, the reference of the level of
+    /// detail and the `parents` of the rotators, and if they `moved`, once for all the rotators
+    /// sharing them
+    fn cache_targets(
+        &mut self,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+        moved: impl Fn(Entity) -> bool,
+        parents: impl Iterator<Item = Entity>,
+    ) {
+        self.target_transforms.clear();
+        self.moved_targets.clear();
+        self.parent_transforms.clear();
+        for parent in parents {
+            self.parent_transforms
+                .entry(parent)
+                .or_insert_with(|| (global_transform(parent), moved(parent)));
+        }
+        let targets = self
+            .rotate_tos
+            .iter()
+            .filter_map(|rotate_to| rotate_to.target.entity())
+            .chain(self.lod.as_deref().map(|lod| lod.reference));
+        for target in targets {
+            if self.target_transforms.contains_key(&target) {
+                continue;
+            }
+            if let Some(target_gt) = global_transform(target) {
+                self.target_transforms.insert(target, target_gt);
+            }
+            if moved(target) {
+                self.moved_targets.insert(target);
+            }
+        }
+    }
+
+    /// The global transform of the parent of the rotator, looked up before the rotators are
+    /// rotated, `None` without a parent, and `Some(None)` if it could not be found
+    fn parent_global_transform(
+        &self,
+        rotator: &RotatorItem,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<Option<GlobalTransform>> {
+        let parent = rotator.child_of?.parent();
+        // the parents of the rotators using the propagated transforms, and of the chained rotators
+        // moved along with the rotators above them, are not looked up before
+        match self.parent_transforms.get(&parent) {
+            Some((parent_gt, _)) if !rotator.propagated && self.chained_transforms.is_empty() => {
+                Some(*parent_gt)
+            }
+            _ => Some(global_transform(parent)),
+        }
+    }
+
+    /// If the parent of the rotator may have moved since the system last ran
+    fn parent_moved(&self, rotator: &RotatorItem, moved: &impl Fn(Entity) -> bool) -> bool {
+        let Some(child_of) = rotator.child_of else {
+            return false;
+        };
+        match self.parent_transforms.get(&child_of.parent()) {
+            Some((_, parent_moved)) if !rotator.propagated => *parent_moved,
+            _ => moved(child_of.parent()),
+        }
+    }
+
+    /// If the target may have moved, or changed its velocity, since the system last ran
+    fn target_moved(&self, target: Entity, moved: &impl Fn(Entity) -> bool) -> bool {
+        let moved = match self.target_transforms.contains_key(&target) {
+            true => self.moved_targets.contains(&target),
+            false => moved(target),
+        };
+        moved
+            || self
+                .velocities
+                .get(target)
+                .is_ok_and(|velocity| velocity.is_changed())
+    }
+
+    /// If the rotator was left as it was the last time, settled facing its target, and nothing it
+    /// depends on changed since, nor `moved`, so it is left as it is again without resolving its
+    /// target, which makes static scenes nearly free
+    fn still(&self, rotator: &RotatorItem, current: Quat, moved: &impl Fn(Entity) -> bool) -> bool {
+        let Some(cache) = &rotator.cache else {
+            return false;
+        };
+        let epsilon = rotator.epsilon.copied().unwrap_or(*self.epsilon).0;
+        if cache.still != Some(rotator.archetype.id())
+            || !cache
+                .settled
+                .is_some_and(|settled| cache.settled_at(settled, current, epsilon))
+            || self.epsilon.is_changed()
+            || self.missing_target_policy.is_changed()
+            || self.lod.as_ref().is_some_and(|lod| lod.is_changed())
+            || self.is_chained(rotator.entity)
+        {
+            return false;
+        }
+        // the glances and the banking change over time
+        if rotator.modifiers.saccades.is_some() || rotator.modifiers.banking.is_some() {
+            return false;
+        }
+        if moved(rotator.entity) || self.parent_moved(rotator, moved) {
+            return false;
+        }
+        let (target, updir) = match &rotator.targets {
+            (Some(rotate_to), ..) => (rotate_to.target.entity(), rotate_to.updir),
+            (None, Some(rotate_to_direction), _) => (None, rotate_to_direction.updir),
+            // the centroid moves along with any of its entities
+            _ => return false,
+        };
+        let up_entity = match updir {
+            UpDirection::Entity(entity) | UpDirection::TowardEntity(entity) => Some(entity),
+            _ => None,
+        };
+        let lod_reference = self.lod.as_deref().map(|lod| lod.reference);
+        [target, up_entity, lod_reference]
+            .into_iter()
+            .flatten()
+            .all(|entity| !self.target_moved(entity, moved))
+    }
+
+    /// How many turns it takes the time slicing to rotate all the rotators
+    fn slices(&self) -> u32 {
+        self.time_slicing
+            .as_ref()
+            .map_or(1, |time_slicing| time_slicing.slices.max(1))
+    }
+
+    /// If the rotator is skipped for not being visible
+    #[cfg(feature = "bevy_camera")]
+    fn skips_invisible(&self, rotator: &RotatorItem) -> bool {
+        self.skip_invisible.is_some() && is_invisible(&rotator.visibility)
+    }
+
+    /// Without cameras, no rotator is skipped for not being visible
+    #[cfg(not(feature = "bevy_camera"))]
+    fn skips_invisible(&self, _rotator: &RotatorItem) -> bool {
+        false
+    }
+
+    /// Every how many times the systems run the rotator is rotated, with the time slicing and the
+    /// level of detail, `None` if it is beyond all the bands of the level of detail
+    fn period(
+        &self,
+        rotator: &RotatorItem,
+        global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<u32> {
+        let slices = self.slices();
+        let Some(lod) = self.lod.as_deref() else {
+            return Some(slices);
+        };
+        let (Some(reference_gt), Some(rotator_gt)) = (
+            global_transform(lod.reference),
+            global_transform(rotator.entity),
+        ) else {
+            return Some(slices);
+        };
+        let bands = rotator
+            .lod_bands
+            .map_or(lod.bands.as_slice(), |bands| bands.0.as_slice());
+        let distance = reference_gt
+            .translation()
+            .distance(rotator_gt.translation());
+        Some(slices.saturating_mul(lod_interval(bands, distance)?))
+    }
+
+    /// If the rotator is rotated this time, always in the same turn out of every `period`
+    fn takes_turn(&self, rotator: Entity, period: u32) -> bool {
+        rotator
+            .index_u32()
+            .wrapping_add(*self.runs)
+            .is_multiple_of(period)
+    }
+
+    /// Seconds since a rotator rotated once every `period` times was rotated last, estimated
+    /// from the current delta, `None` without [`Time`]
+    fn delta_secs(&self, period: u32) -> Option<f32> {
+        let delta_secs = self.time.as_deref()?.delta_secs();
+        Some(delta_secs * period as f32)
+    }
+
+    /// Calculates the new local rotation of a rotator, currently rotated by `current`,
+    /// or `None` if it should keep its rotation, and counts it in the [`RotatedRotators`]
+    ///
+    /// Only reads the shared parameters, so the rotators can be rotated in parallel, the events
+    /// and the changes to the [`LostTargets`] are queued on the `commands` of the rotator.
+    fn rotate(
+        &self,
+        commands: &mut Commands,
+        rotator: &mut RotatorItem,
+        current: Quat,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+        moved: impl Fn(Entity) -> bool,
+    ) -> Option<Quat> {
+        let rotation = self.new_rotation(commands, rotator, current, global_transform, moved);
+        if let (Some(rotated), Some(_)) = (&self.rotated, rotation) {
+            rotated.0.fetch_add(1, Ordering::Relaxed);
+        }
+        rotation
+    }
+
+    /// The new local rotation of a rotator, see [`RotateParams::rotate`]
+    fn new_rotation(
+        &self,
+        commands: &mut Commands,
+        rotator: &mut RotatorItem,
+        current: Quat,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+        moved: impl Fn(Entity) -> bool,
+    ) -> Option<Quat> {
+        if self.skips_invisible(rotator) {
+            return rotator.stop();
+        }
+        if self.still(rotator, current, &moved) {
+            return None;
+        }
+        if let Some(cache) = &mut rotator.cache {
+            cache.still = None;
+        }
+        let global_transform = |entity| {
+            // while the chains are rotated, the targets may have moved along with them
+            if !self.chained_transforms.is_empty() {
+                return self.chained_global_transform(entity, &global_transform);
+            }
+            self.target_transforms
+                .get(&entity)
+                .copied()
+                .or_else(|| global_transform(entity))
+        };
+        let Some(period) = self.period(rotator, &global_transform) else {
+            return rotator.stop();
+        };
+        if !self.takes_turn(rotator.entity, period) {
+            return None;
+        }
+        let delta_secs = self.delta_secs(period);
+        let (rotate_to, rotate_to_direction, rotate_to_centroid) = &rotator.targets;
+        if rotate_to
+            .as_ref()
+            .is_some_and(|rotate_to| !rotate_to.enabled)
+        {
+            return rotator.stop();
+        }
+        let Some((target, updir)) = ResolvedTarget::resolve_any(
+            rotate_to.as_deref(),
+            rotate_to_direction.as_deref(),
+            rotate_to_centroid.as_deref(),
+            rotator.look.screen_aligned,
+            &global_transform,
+        ) else {
+            if let Some(target) = rotate_to.as_ref().and_then(|r| r.target.entity()) {
+                let policy = rotator
+                    .gates
+                    .missing_target_policy
+                    .copied()
+                    .unwrap_or(*self.missing_target_policy);
+                self.missing_target(commands, policy, rotator.entity, target);
+            }
+            return self.idle_wander(rotator, current, delta_secs, &global_transform);
+        };
+        if self.lost.0.contains(&rotator.entity) {
+            set_lost(commands, rotator.entity, false);
+            if let Some(target) = rotate_to.as_ref().and_then(|r| r.target.entity()) {
+                commands.trigger(TargetReacquired {
+                    entity: rotator.entity,
+                    target,
+                });
+            }
+        }
+
+        let occluded = rotator
+            .gates
+            .line_of_sight
+            .is_some_and(|(_, state)| !state.visible);
+        // turn towards where an occluded target was last seen, while it is remembered
+        let remembered = match rotator.gates.line_of_sight {
+            Some((LineOfSight::Track, state)) if occluded => {
+                match (rotator.gates.line_of_sight_memory, state.last_seen) {
+                    (Some(memory), Some(last_seen))
+                        if state.occluded_secs <= memory.duration_secs =>
+                    {
+                        Some(last_seen)
+                    }
+                    _ => return self.idle_wander(rotator, current, delta_secs, &global_transform),
+                }
+            }
+            _ => None,
+        };
+        let target = match remembered {
+            Some(last_seen) => ResolvedTarget::Point(last_seen, target.up()),
+            None => target,
+        };
+
+        // a broken hierarchy only keeps the rotator from rotating, rather than the rest of them
+        let rotator_gt = global_transform(rotator.entity);
+        let parent_gt = self.parent_global_transform(rotator, global_transform);
+        let (Some(rotator_gt), None | Some(Some(_))) = (rotator_gt, parent_gt) else {
+            self.broken_hierarchy(commands, rotator.entity, true);
+            return rotator.stop();
+        };
+        let parent_gt = parent_gt.flatten();
+        self.broken_hierarchy(commands, rotator.entity, false);
+
+        let updir = resolve_updir(
+            updir,
+            target.up(),
+            parent_gt.as_ref(),
+            &rotator_gt,
+            rotator.surface_normal.and_then(|state| state.normal),
+            self.velocities
+                .get(rotator.entity)
+                .ok()
+                .map(|velocity| velocity.0),
+            &global_transform,
+        );
+
+        if let Some((range, state)) = &mut rotator.gates.engagement_range {
+            let in_range = match target {
+                ResolvedTarget::Point(point, _) => {
+                    range.contains(rotator_gt.translation().distance(point))
+                }
+                // a direction is not at any distance
+                ResolvedTarget::Direction(..) => true,
+            };
+            if in_range != state.in_range {
+                state.in_range = in_range;
+                let entity = rotator.entity;
+                if in_range {
+                    commands.trigger(TargetEnteredRange { entity });
+                } else {
+                    commands.trigger(TargetLeftRange { entity });
+                }
+            }
+            match range.outside {
+                _ if in_range => {}
+                OutsideRange::Freeze => return rotator.stop(),
+                OutsideRange::ReturnToRest(rest) => {
+                    let new_rotation = rotator.approach(current, rest, delta_secs);
+                    return self.output(
+                        rotator,
+                        current,
+                        new_rotation,
+                        parent_gt.as_ref(),
+                        delta_secs,
+                    );
+                }
+            }
+        }
+
+        if let Some((field_of_view, state)) = &mut rotator.gates.field_of_view {
+            let forward = rotator
+                .look
+                .forward_axis
+                .as_ref()
+                .map_or(Dir3::NEG_Z, |axis| axis.0);
+            // turned by the matrices, the rotations are not decomposed out of them
+            let forward = match field_of_view.reference {
+                FieldOfViewReference::Current => rotator_gt.affine().transform_vector3(*forward),
+                FieldOfViewReference::Parent => direction_from_parent(*forward, parent_gt.as_ref()),
+                FieldOfViewReference::Rest(rest) => {
+                    direction_from_parent(rest * *forward, parent_gt.as_ref())
+                }
+            };
+            let direction = target.direction_from(&rotator_gt);
+            let direction = if rotator.look.away {
+                -direction
+            } else {
+                direction
+            };
+            let in_view = field_of_view.contains(direction, forward);
+            if in_view != state.in_view {
+                state.in_view = in_view;
+                let entity = rotator.entity;
+                if in_view {
+                    commands.trigger(TargetEnteredView { entity });
+                } else {
+                    commands.trigger(TargetLeftView { entity });
+                }
+            }
+            if !in_view {
+                return rotator.stop();
+            }
+        }
+
+        // aim ahead of a moving target
+        let velocity = rotate_to
+            .as_ref()
+            .and_then(|r| r.target.entity())
+            .filter(|_| remembered.is_none())
+            .and_then(|target| self.velocities.get(target).ok());
+        let target = match (target, rotator.look.aim_lead, velocity) {
+            (ResolvedTarget::Point(point, up), Some(aim_lead), Some(velocity)) => {
+                let point = aim_lead.predict(rotator_gt.translation(), point, velocity.0);
+                ResolvedTarget::Point(point, up)
+            }
+            (target, ..) => target,
+        };
+        let target = if rotator.look.away {
+            target.mirrored(&rotator_gt)
+        } else {
+            target
+        };
+        let epsilon = rotator.epsilon.copied().unwrap_or(*self.epsilon).0;
+        if target.co_located(&rotator_gt, epsilon) {
+            match rotator.gates.co_located.copied().unwrap_or_default() {
+                CoLocatedTarget::Freeze => return rotator.stop(),
+                CoLocatedTarget::ReturnToRest(rest) => {
+                    let new_rotation = rotator.approach(current, rest, delta_secs);
+                    return self.output(
+                        rotator,
+                        current,
+                        new_rotation,
+                        parent_gt.as_ref(),
+                        delta_secs,
+                    );
+                }
+            }
+        }
+
+        // the glances keep their own time, whether the look rotation is cached or not
+        let glance = match (
+            &mut rotator.modifiers.saccades,
+            rotator.look.rotate_2d.is_some(),
+            delta_secs,
+        ) {
+            (Some((saccades, state)), false, Some(delta_secs)) => {
+                saccades.glance(state, delta_secs)
+            }
+            _ => Quat::IDENTITY,
+        };
+
+        // the look rotation is only calculated again once something it depends on changed
+        let inputs = RotateInputs {
+            rotator_gt,
+            parent_gt,
+            target,
+            updir,
+            config: rotator.look.config(),
+        };
+        let cached_look = rotator
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.look(&inputs, epsilon));
+
+        let offset = rotator
+            .look
+            .offset
+            .as_ref()
+            .map_or(Quat::IDENTITY, |offset| offset.0);
+        let adjustment = match rotator.look.rotate_2d {
+            Some(_) => offset,
+            None => {
+                offset
+                    * rotator
+                        .look
+                        .forward_axis
+                        .as_ref()
+                        .map_or(Quat::IDENTITY, |forward_axis| forward_axis.correction())
+            }
+        };
+
+        // the look rotation points Vec3::NEG_Z at the target, the adjustment is applied after it
+        let look_at = |target: ResolvedTarget| {
+            // like when a pivot shifts the target onto the rotator
+            if target.co_located(&rotator_gt, epsilon) {
+                return None;
+            }
+            let target = if rotator.look.cylindrical.is_some() {
+                target.upright(&rotator_gt, updir)?
+            } else {
+                target
+            };
+            match (
+                rotator.look.rotate_2d.as_deref(),
+                rotator.look.axis_lock.as_deref(),
+            ) {
+                (Some(rotate_2d), _) => {
+                    target.local_rotation_2d(&rotator_gt, parent_gt.as_ref(), rotate_2d)
+                }
+                (None, Some(axis_lock)) => calculate_local_rotation_around_axis(
+                    target.direction_from(&rotator_gt),
+                    parent_gt.as_ref(),
+                    axis_lock.axis(),
+                ),
+                (None, None) => {
+                    let (target, updir) = rotator
+                        .look
+                        .pole
+                        .as_deref()
+                        .copied()
+                        .unwrap_or_default()
+                        .stabilize_target(target, updir, &rotator_gt);
+                    Some(target.local_rotation(&rotator_gt, parent_gt.as_ref(), updir))
+                }
+            }
+        };
+        let look = cached_look.or_else(|| {
+            let mut look = look_at(target)?;
+            // where the pivot is depends on the rotation, so refine the rotation a few times,
+            // which settles quickly while the target is further away than the pivot is from the origin
+            if let (Some(pivot), ResolvedTarget::Point(point, up)) =
+                (rotator.look.pivot.as_deref(), target)
+            {
+                let scale = rotator_gt.scale();
+                for _ in 0..PIVOT_ITERATIONS {
+                    // the pivot turned into world space by the parent, like the local rotation
+                    let offset = direction_from_parent(
+                        look * adjustment * (scale * pivot.0),
+                        parent_gt.as_ref(),
+                    );
+                    let shifted = point - offset;
+                    look = look_at(ResolvedTarget::Point(shifted, up))?;
+                }
+            }
+            Some(look)
+        });
+        let Some(look) = look else {
+            return rotator.stop();
+        };
+        if let Some(cache) = &mut rotator.cache {
+            cache.look = Some((inputs, look));
+        }
+        // the look rotation facing the target, regardless of the limits of the rotator
+        let target_look = look;
+        let look = look * glance;
+        let look = match &mut rotator.modifiers.cone_limit {
+            Some((cone_limit, state)) => {
+                let (look, out_of_range) = cone_limit.clamp(look, adjustment);
+                if out_of_range != state.out_of_range {
+                    state.out_of_range = out_of_range;
+                    if out_of_range && cone_limit.trigger_out_of_range {
+                        commands.trigger(TargetOutOfRange {
+                            entity: rotator.entity,
+                        });
+                    }
+                }
+                look
+            }
+            None => look,
+        };
+        let look = match rotator.modifiers.joint_limits {
+            Some(joint_limits) => joint_limits.limit(look, adjustment),
+            None => look,
+        };
+
+        let adjustment = rotator.bank(look, delta_secs) * adjustment;
+        let rotation = look * adjustment;
+        let desired = rotation;
+        let rotation = match rotator.modifiers.axis_weights {
+            Some(axis_weights) => {
+                // the up direction in the space of the local rotation
+                let up = Dir3::new(direction_in_parent(updir.as_vec3(), parent_gt.as_ref()))
+                    .unwrap_or(updir);
+                axis_weights.weigh(current, rotation, up)
+            }
+            None => rotation,
+        };
+        let rotation = match &mut rotator.modifiers.weight {
+            Some((weight, state)) => state.weigh(**weight, current, rotation),
+            None => rotation,
+        };
+
+        // measured along the forward axis of the model, without the offset
+        let rotate_2d = rotator.look.rotate_2d.is_some();
+        let angle_from = |rotation: Quat| {
+            let seen = rotation * adjustment.inverse();
+            if rotate_2d {
+                AngleToTarget::new_2d(seen, target_look)
+            } else {
+                AngleToTarget::new(seen, target_look)
+            }
+        };
+
+        // turning gradually to a target about behind it, it keeps turning the way it did
+        let gradual = delta_secs.is_some()
+            && (rotator.motion.smoothing.is_some() || rotator.motion.speed_limit.is_some());
+        let rotation = match (
+            rotator.look.axis_lock.as_deref(),
+            &mut rotator.modifiers.axis_lock,
+            rotator.look.rotate_2d.is_some(),
+        ) {
+            (Some(axis_lock), Some(state), false) if gradual => {
+                state.hold_turn(axis_lock.axis(), current, rotation)
+            }
+            _ => rotation,
+        };
+
+        // within the deadband, the rotator keeps facing where it does
+        let deadbanded = rotator
+            .modifiers
+            .deadband
+            .is_some_and(|deadband| angle_from(current).degrees <= deadband.degrees);
+        let new_rotation = if deadbanded {
+            current
+        } else {
+            rotator.approach(current, rotation, delta_secs)
+        };
+
+        if let Some((_, state)) = &mut rotator.modifiers.weight {
+            state.weighted = Some(new_rotation);
+        }
+
+        let output = self.output(
+            rotator,
+            current,
+            new_rotation,
+            parent_gt.as_ref(),
+            delta_secs,
+        );
+        rotator.outputs.measure(angle_from(new_rotation), occluded);
+        // a settled rotator is not rotated again, while it still faces where it should
+        let settled = output == Some(desired);
+        let unchanged = settled
+            && rotator
+                .cache
+                .as_ref()
+                .is_some_and(|cache| cache.settled_at(desired, current, epsilon));
+        if let Some(cache) = &mut rotator.cache {
+            cache.settled = settled.then_some(desired);
+            cache.still = unchanged.then_some(rotator.archetype.id());
+        }
+        output.filter(|_| !deadbanded && !unchanged)
+    }
+
+    /// The `rotator_t` of a rotator with its new rotation, or `None` if it should keep its rotation
+    fn rotate_transform(
+        &self,
+        commands: &mut Commands,
+        rotator: &mut RotatorItem,
+        rotator_t: &Transform,
+        global_transform: impl Fn(Entity) -> Option<GlobalTransform>,
+        moved: impl Fn(Entity) -> bool,
+    ) -> Option<Transform> {
+        let current = rotator.current_rotation(rotator_t);
+        let rotation = self.rotate(commands, rotator, current, global_transform, moved);
+        let rotation = rotator.blend_layer(rotator_t.rotation, rotation)?;
+        Some(rotator_t.with_rotation(rotation))
+    }
+
+    /// Turns the rotator from `current` to `new_rotation`, returns `new_rotation` when it is set
+    /// on the [`Transform`], the other outputs are set by [`output_rotations`]
+    fn output(
+        &self,
+        rotator: &mut RotatorItem,
+        current: Quat,
+        new_rotation: Quat,
+        parent_gt: Option<&GlobalTransform>,
+        delta_secs: Option<f32>,
+    ) -> Option<Quat> {
+        rotator.outputs.solve(RotateSolution {
+            turn: Some(RotateTurn {
+                current,
+                rotation: new_rotation,
+                parent_gt: parent_gt.copied(),
+                delta_secs: delta_secs.unwrap_or(0.0),
+            }),
+            ..Default::default()
+        });
+        rotator.outputs.transform().then_some(new_rotation)
+    }
+
+    /// Wanders the gaze of a rotator with a [`RotateIdleWander`], while it has no target, stops it
+    /// without one
+    fn idle_wander(
+        &self,
+        rotator: &mut RotatorItem,
+        current: Quat,
+        delta_secs: Option<f32>,
+        global_transform: &impl Fn(Entity) -> Option<GlobalTransform>,
+    ) -> Option<Quat> {
+        let (Some((wander, state)), Some(wander_secs)) =
+            (rotator.motion.idle_wander.as_mut(), delta_secs)
+        else {
+            return rotator.stop();
+        };
+        let wandering = wander.wander(state, wander_secs);
+        let parent_gt = self
+            .parent_global_transform(rotator, global_transform)
+            .flatten();
+        let new_rotation = rotator.approach(current, wandering, delta_secs);
+        self.output(
+            rotator,
+            current,
+            new_rotation,
+            parent_gt.as_ref(),
+            delta_secs,
+        )
+    }
+
+    /// Keeps track of the rotators with a [`BrokenHierarchies`], warning once when one breaks
+    fn broken_hierarchy(&self, commands: &mut Commands, rotator: Entity, broken: bool) {
+        if broken == self.broken.0.contains(&rotator) {
+            return;
+        }
+        if broken {
+            warn!(
+                "Entity {rotator} is not rotated, since the GlobalTransform of it or its parent could not be found, an ancestor may be missing a Transform"
+            );
+        }
+        commands.queue(move |world: &mut World| {
+            let Some(mut broken_hierarchies) = world.get_resource_mut::<BrokenHierarchies>() else {
+                return;
+            };
+            if broken {
+                broken_hierarchies.0.insert(rotator);
+            } else {
+                broken_hierarchies.0.remove(&rotator);
+            }
+        });
+    }
+
+    /// Applies the [`MissingTargetPolicy`] of a rotator whose targeted entity could not be found
+    fn missing_target(
+        &self,
+        commands: &mut Commands,
+        policy: MissingTargetPolicy,
+        rotator: Entity,
+        target: Entity,
+    ) {
+        let newly_lost = !self.lost.0.contains(&rotator);
+        if newly_lost {
+            commands.trigger(TargetLost {
+                entity: rotator,
+                target,
+            });
+        }
+
+        match policy {
+            MissingTargetPolicy::WarnOnce => {
+                if newly_lost {
+                    warn_missing_target(rotator, target);
+                }
+            }
+            MissingTargetPolicy::Ignore => {}
+            MissingTargetPolicy::RemoveComponent => {
+                if !newly_lost {
+                    set_lost(commands, rotator, false);
+                }
+                if let Ok(mut ec) = commands.get_entity(rotator) {
+                    ec.try_remove::<RotateTo>();
+                }
+                return;
+            }
+            MissingTargetPolicy::ClearTarget => {
+                if !newly_lost {
+                    set_lost(commands, rotator, false);
+                }
+                commands.queue(move |world: &mut World| {
+                    let Some(mut rotate_to) = world.get_mut::<RotateTo>(rotator) else {
+                        return;
+                    };
+                    // unless it was retargeted since
+                    if rotate_to.target == RotateTarget::Entity(target) {
+                        rotate_to.target = RotateTarget::default();
+                    }
+                });
+                return;
+            }
+            MissingTargetPolicy::DespawnRotator => {
+                if !newly_lost {
+                    set_lost(commands, rotator, false);
+                }
+                if let Ok(mut ec) = commands.get_entity(rotator) {
+                    ec.try_despawn();
+                }
+                return;
+            }
+            MissingTargetPolicy::EmitEvent => {
+                commands.trigger(RotateTargetMissing {
+                    entity: rotator,
+                    target,
+                });
+            }
+        }
+        if newly_lost {
+            set_lost(commands, rotator, true);
+        }
+    }
+}
+
+/// Queues adding the rotator to, or removing it from, the [`LostTargets`]
+fn set_lost(commands: &mut Commands, rotator: Entity, lost: bool) {
+    commands.queue(move |world: &mut World| {
+        let Some(mut lost_targets) = world.get_resource_mut::<LostTargets>() else {
+            return;
+        };
+        if lost {
+            lost_targets.0.insert(rotator);
+        } else {
+            lost_targets.0.remove(&rotator);
+        }
+    });
+}
+
+/// The parents of the rotators, besides the ones using the propagated transforms
+fn parents_of<'a, D: QueryData>(
+    rotators: &'a Query<(D, Rotator)>,
+) -> impl Iterator<Item = Entity> + 'a {
+    rotators
+        .iter()
+        .filter(|(_, rotator)| !rotator.propagated)
+        .filter_map(|(_, rotator)| Some(rotator.child_of?.parent()))
+}
+
+pub(crate) fn rotate_towards_without_updating_global_transforms(
+    global_transforms: Query<Ref<GlobalTransform>>, // potential_targets
+    mut rotators: Query<(&mut Transform, Rotator)>, // the ones to rotate
+    mut params: RotateParams,
+) {
+    let global_transform = |e| global_transforms.get(e).ok().map(|gt| *gt);
+    // the propagated global transforms of the entities that moved were changed since
+    let moved = |e| global_transforms.get(e).map_or(true, |gt| gt.is_changed());
+    params.next_run();
+    params.cache_targets(global_transform, moved, parents_of(&rotators));
+    params.find_chains(
+        rotators
+            .iter()
+            .filter(|(_, rotator)| rotator.child_of.is_some())
+            .map(|(_, rotator)| rotator.entity),
+        |e| rotators.contains(e),
+    );
+    {
+        let params = &params;
+        rotators
+            .par_iter_mut()
+            .for_each(|(mut rotator_t, mut rotator)| {
+                if params.is_chained(rotator.entity) {
+                    return;
+                }
+                let rotation = params.commands.command_scope(|mut commands| {
+                    let current = rotator.current_rotation(&rotator_t);
+                    params.rotate(
+                        &mut commands,
+                        &mut rotator,
+                        current,
+                        global_transform,
+                        moved,
+                    )
+                });
+                if let Some(rotation) = rotator.blend_layer(rotator_t.rotation, rotation) {
+                    rotator_t.rotation = rotation;
+                }
+            });
+    }
+
+    // the rotators within the hierarchy of other rotators rotate from where those are rotated to
+    for entity in params.chain_order() {
+        let Ok((mut rotator_t, mut rotator)) = rotators.get_mut(entity) else {
+            continue;
+        };
+        let rotation = params.commands.command_scope(|mut commands| {
+            let current = rotator.current_rotation(&rotator_t);
+            params.rotate(
+                &mut commands,
+                &mut rotator,
+                current,
+                global_transform,
+                moved,
+            )
+        });
+        if let Some(rotation) = rotator.blend_layer(rotator_t.rotation, rotation) {
+            rotator_t.rotation = rotation;
+            params.chained_rotated(entity, *rotator_t, global_transform);
+        }
+    }
+}
+
+pub(crate) fn rotate_towards_with_updated_global_transforms(
+    mut rotators: Query<(
+        &Transform, // cant have mut access here, will conflict with TransformHelper
+        Rotator,
+    )>, // the ones to rotate
+    trans_helper: TransformHelper,
+    propagated_transforms: Query<Ref<GlobalTransform>>,
+    hierarchy: Query<(Ref<Transform>, Option<Ref<ChildOf>>)>,
+    mut params: RotateParams,
+) {
+    let global_transform = |e| trans_helper.compute_global_transform(e).ok();
+    // the global transform is calculated from the transforms up the hierarchy, an entity moved if
+    // one of them changed since, without calculating it
+    let moved = |mut e| loop {
+        let Ok((transform, child_of)) = hierarchy.get(e) else {
+            return true;
+        };
+        if transform.is_changed() {
+            return true;
+        }
+        let Some(child_of) = child_of else {
+            return false;
+        };
+        if child_of.is_changed() {
+            return true;
+        }
+        e = child_of.parent();
+    };
+    // rotators using the propagated transforms only use the new ones of the shared targets
+    let rotator_global_transform = |propagated: bool| {
+        move |e| match propagated {
+            true => propagated_transforms.get(e).ok().map(|gt| *gt),
+            false => global_transform(e),
+        }
+    };
+    let rotator_moved = |propagated: bool| {
+        move |e| match propagated {
+            true => propagated_transforms
+                .get(e)
+                .map_or(true, |gt| gt.is_changed()),
+            false => moved(e),
+        }
+    };
+    params.next_run();
+    params.cache_targets(global_transform, moved, parents_of(&rotators));
+    params.find_chains(
+        rotators
+            .iter()
+            .filter(|(_, rotator)| rotator.child_of.is_some())
+            .map(|(_, rotator)| rotator.entity),
+        |e| rotators.contains(e),
+    );
+    {
+        let params = &params;
+        rotators
+            .par_iter_mut()
+            .for_each(|(rotator_t, mut rotator)| {
+                if params.is_chained(rotator.entity) {
+                    return;
+                }
+                let global_transform = rotator_global_transform(rotator.propagated);
+                let moved = rotator_moved(rotator.propagated);
+                params.commands.command_scope(|mut commands| {
+                    if let Some(rotator_t) = params.rotate_transform(
+                        &mut commands,
+                        &mut rotator,
+                        rotator_t,
+                        global_transform,
+                        moved,
+                    ) {
+                        insert_transform(&mut commands, rotator.entity, rotator_t);
+                    }
+                });
+            });
+    }
+
+    // the rotators within the hierarchy of other rotators rotate from where those are rotated to
+    for entity in params.chain_order() {
+        let Ok((rotator_t, mut rotator)) = rotators.get_mut(entity) else {
+            continue;
+        };
+        let global_transform = rotator_global_transform(rotator.propagated);
+        let moved = rotator_moved(rotator.propagated);
+        let rotator_t = params.commands.command_scope(|mut commands| {
+            let rotator_t = params.rotate_transform(
+                &mut commands,
+                &mut rotator,
+                rotator_t,
+                global_transform,
+                moved,
+            )?;
+            insert_transform(&mut commands, entity, rotator_t);
+            Some(rotator_t)
+        });
+        if let Some(rotator_t) = rotator_t {
+            params.chained_rotated(entity, rotator_t, global_transform);
+        }
+    }
+}
+
+/// Sets the new `Transform` of a rotator
+fn insert_transform(commands: &mut Commands, rotator: Entity, rotator_t: Transform) {
+    // workaround since if we have a mutable access to Transforms in the rotators query,
+    // we will create a Query Conflict panic
+    let Ok(mut ec) = commands.get_entity(rotator) else {
+        return;
+    };
+
+    ec.try_insert(rotator_t);
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_8};
+
+    use bevy_app::prelude::*;
+
+    use super::*;
+    use crate::{acquisition::RotateToAny, RotateTowardsPlugin};
+
+    /// An app rotating from the global transforms it is given, without propagating them
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        app
+    }
+
+    fn spawn_rotator(app: &mut App, rotation: Quat, bundle: impl Bundle) -> Entity {
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(rotation);
+        app.world_mut()
+            .spawn((transform, GlobalTransform::from(transform), bundle))
+            .id()
+    }
+
+    fn rotation_of(app: &App, entity: Entity) -> Quat {
+        app.world().get::<Transform>(entity).unwrap().rotation
+    }
+
+    #[test]
+    fn co_located_target_keeps_rotation() {
+        let mut app = app();
+        let rotation = Quat::from_rotation_y(0.5);
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(1.0, 2.0, 3.0))
+            .id();
+        let to_entity = spawn_rotator(&mut app, rotation, RotateTo::new(target));
+        let to_point = spawn_rotator(&mut app, rotation, RotateTo::new(Vec3::new(1.0, 2.0, 3.0)));
+        let nearly = spawn_rotator(
+            &mut app,
+            rotation,
+            RotateTo::new(Vec3::new(1.0, 2.0, 3.0 + 1e-6)),
+        );
+        app.update();
+        for rotator in [to_entity, to_point, nearly] {
+            assert_eq!(rotation_of(&app, rotator), rotation);
+        }
+    }
+
+    #[test]
+    fn co_located_target_returns_to_rest() {
+        let mut app = app();
+        let rest = Quat::from_rotation_x(0.3);
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::from_rotation_y(0.5),
+            (
+                RotateTo::new(Vec3::new(1.0, 2.0, 3.0)),
+                CoLocatedTarget::ReturnToRest(rest),
+            ),
+        );
+        app.update();
+        assert!(rotation_of(&app, rotator).abs_diff_eq(rest, 1e-6));
+    }
+
+    #[test]
+    fn target_moving_away_is_faced_again() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(1.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        app.update();
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+        *app.world_mut().get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(5.0, 2.0, 3.0);
+        app.update();
+        let forward = rotation_of(&app, rotator) * Vec3::NEG_Z;
+        assert!(forward.abs_diff_eq(Vec3::X, 1e-5), "{forward}");
+    }
+
+    #[test]
+    fn settled_rotator_follows_config_changes() {
+        let mut app = app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(5.0, 2.0, 3.0)),
+        );
+        app.update();
+        app.update();
+        let facing = rotation_of(&app, rotator);
+        assert!((facing * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+
+        // the rotator settled, with the same inputs it is not rotated again
+        let offset = Quat::from_rotation_y(0.5);
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateOffset(offset));
+        app.update();
+        assert!(rotation_of(&app, rotator).abs_diff_eq(facing * offset, 1e-5));
+        app.world_mut().get_mut::<RotateOffset>(rotator).unwrap().0 = Quat::IDENTITY;
+        app.update();
+        assert!(rotation_of(&app, rotator).abs_diff_eq(facing, 1e-5));
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateOffset(offset));
+        app.update();
+        app.world_mut().entity_mut(rotator).remove::<RotateOffset>();
+        app.update();
+        assert!(rotation_of(&app, rotator).abs_diff_eq(facing, 1e-5));
+    }
+
+    #[test]
+    fn settled_rotator_follows_limit_changes() {
+        let mut app = app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(5.0, 2.0, 3.0)),
+        );
+        app.update();
+        app.update();
+        let facing = rotation_of(&app, rotator);
+
+        // the limits are applied to the cached look rotation
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateConeLimit::new(30.0));
+        app.update();
+        let angle = forward_of(&app, rotator).angle_between(Vec3::NEG_Z);
+        assert!((angle.to_degrees() - 30.0).abs() < 1e-3);
+        app.world_mut()
+            .entity_mut(rotator)
+            .remove::<RotateConeLimit>();
+        app.update();
+        assert!(rotation_of(&app, rotator).abs_diff_eq(facing, 1e-5));
+
+        app.world_mut()
+            .get_mut::<Transform>(rotator)
+            .unwrap()
+            .rotation = Quat::IDENTITY;
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateWeight(0.5));
+        app.update();
+        let angle = forward_of(&app, rotator).angle_between(Vec3::NEG_Z);
+        assert!((angle.to_degrees() - 45.0).abs() < 1e-3);
+    }
+
+    /// An app, like [`app`], whose time advances by a tenth of a second every update
+    fn timed_app() -> App {
+        let mut app = app();
+        let mut time = Time::<()>::default();
+        time.advance_by(core::time::Duration::from_millis(100));
+        app.insert_resource(time);
+        app
+    }
+
+    fn angular_velocity_of(app: &App, entity: Entity) -> Vec3 {
+        app.world().get::<RotateAngularVelocity>(entity).unwrap().0
+    }
+
+    #[test]
+    fn angular_velocity_stops_with_tracking() {
+        let mut app = timed_app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(5.0, 2.0, 3.0)),
+                RotateAngularVelocity::default(),
+            ),
+        );
+        app.update();
+        assert_ne!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = false;
+        app.update();
+        assert_eq!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = true;
+        app.update();
+        assert_ne!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+        app.world_mut().entity_mut(rotator).remove::<RotateTo>();
+        app.update();
+        assert_eq!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+    }
+
+    #[test]
+    fn angular_velocity_stops_outside_the_field_of_view() {
+        let mut app = timed_app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(2.0, 2.0, -2.0)),
+                FieldOfView::default(),
+                RotateAngularVelocity::default(),
+            ),
+        );
+        app.update();
+        assert_ne!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+        // behind the rotator
+        app.world_mut().get_mut::<RotateTo>(rotator).unwrap().target =
+            Vec3::new(1.0, 2.0, 10.0).into();
+        app.update();
+        assert_eq!(angular_velocity_of(&app, rotator), Vec3::ZERO);
+    }
+
+    fn torque_of(app: &App, entity: Entity) -> Vec3 {
+        app.world().get::<RotateTorqueState>(entity).unwrap().torque
+    }
+
+    #[test]
+    fn torque_stops_with_tracking() {
+        let mut app = timed_app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(5.0, 2.0, 3.0)),
+                RotateTorque::default(),
+            ),
+        );
+        app.update();
+        assert_ne!(torque_of(&app, rotator), Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = false;
+        app.update();
+        assert_eq!(torque_of(&app, rotator), Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = true;
+        app.update();
+        assert_ne!(torque_of(&app, rotator), Vec3::ZERO);
+        app.world_mut().entity_mut(rotator).remove::<RotateTo>();
+        app.update();
+        assert_eq!(torque_of(&app, rotator), Vec3::ZERO);
+    }
+
+    #[test]
+    fn chained_rotator_rotates_from_the_rotated_parent() {
+        let mut app = app();
+        let child_transform = Transform::from_xyz(0.0, 0.0, -2.0);
+        let child = app
+            .world_mut()
+            .spawn((
+                child_transform,
+                GlobalTransform::from(child_transform),
+                RotateTo::new(Vec3::new(2.0, 0.0, -10.0)),
+            ))
+            .id();
+        let parent = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::new(10.0, 0.0, 0.0)),
+            ))
+            .add_child(child)
+            .id();
+        app.update();
+
+        // the parent moved the child in front of its target within the same frame
+        let parent_forward = rotation_of(&app, parent) * Vec3::NEG_Z;
+        assert!(
+            parent_forward.abs_diff_eq(Vec3::X, 1e-5),
+            "{parent_forward}"
+        );
+        let forward = rotation_of(&app, parent) * rotation_of(&app, child) * Vec3::NEG_Z;
+        assert!(forward.abs_diff_eq(Vec3::NEG_Z, 1e-5), "{forward}");
+    }
+
+    #[test]
+    fn rotated_rotators_are_counted_without_propagating() {
+        let mut app = app();
+        app.init_resource::<RotatedRotators>();
+        spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(5.0, 2.0, 3.0)),
+        );
+        spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(1.0, 2.0, 3.0)),
+        );
+        app.update();
+        let rotated = app.world().resource::<RotatedRotators>();
+        assert_eq!(rotated.0.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn rotating_to_is_synced_in_the_schedule_of_the_plugin() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false).in_schedule(Update));
+        let target = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        app.world_mut().run_schedule(Update);
+        let rotating_to = app.world().get::<RotatingTo>(rotator).map(|r| r.0);
+        assert_eq!(rotating_to, Some(target));
+
+        app.world_mut().entity_mut(rotator).remove::<RotateTo>();
+        app.world_mut().run_schedule(Update);
+        assert!(app.world().get::<RotatingTo>(rotator).is_none());
+    }
+
+    #[test]
+    fn rotating_to_is_synced_while_paused() {
+        let mut app = app();
+        app.insert_resource(crate::RotateTowardsEnabled(false));
+        let first = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let second = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let rotating_to = |app: &App, rotator| app.world().get::<RotatingTo>(rotator).map(|r| r.0);
+
+        // right away, without the schedule running
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(first));
+        assert_eq!(rotating_to(&app, rotator), Some(first));
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateTo::new(second));
+        assert_eq!(rotating_to(&app, rotator), Some(second));
+        assert!(app.world().get::<RotatedToBy>(first).is_none());
+
+        // changed in place, once the schedule runs, even with its systems paused
+        app.world_mut().get_mut::<RotateTo>(rotator).unwrap().target = first.into();
+        app.update();
+        assert_eq!(rotating_to(&app, rotator), Some(first));
+
+        app.world_mut().entity_mut(rotator).remove::<RotateTo>();
+        assert_eq!(rotating_to(&app, rotator), None);
+    }
+
+    #[test]
+    fn rotating_to_is_synced_once_retargeted() {
+        let mut app = app();
+        let first = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let second = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateToAny::new([first.into(), second.into()]),
+        );
+        let rotating_to = |app: &App| app.world().get::<RotatingTo>(rotator).map(|r| r.0);
+        let rotated_by = |app: &App, target| {
+            app.world()
+                .get::<RotatedToBy>(target)
+                .is_some_and(|by| by.contains(rotator))
+        };
+        app.update();
+        assert_eq!(rotating_to(&app), Some(first));
+        assert!(rotated_by(&app, first));
+
+        app.world_mut().despawn(first);
+        app.update();
+        assert_eq!(rotating_to(&app), Some(second));
+        assert!(rotated_by(&app, second));
+    }
+
+    #[test]
+    fn despawned_target_releases_its_rotators() {
+        #[derive(Resource, Default)]
+        struct Triggered {
+            lost: u32,
+            missing: u32,
+        }
+
+        let mut app = app();
+        app.init_resource::<Triggered>()
+            .add_observer(|_: On<TargetLost>, mut triggered: ResMut<Triggered>| {
+                triggered.lost += 1;
+            })
+            .add_observer(
+                |_: On<RotateTargetMissing>, mut triggered: ResMut<Triggered>| {
+                    triggered.missing += 1;
+                },
+            );
+        let target = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let mut spawn = |policy| {
+            let rotate_to = RotateTo::new(target).with_target_offset(Vec3::Y);
+            spawn_rotator(&mut app, Quat::IDENTITY, (rotate_to, policy))
+        };
+        let warn = spawn(MissingTargetPolicy::WarnOnce);
+        let event = spawn(MissingTargetPolicy::EmitEvent);
+        let remove = spawn(MissingTargetPolicy::RemoveComponent);
+        let despawn = spawn(MissingTargetPolicy::DespawnRotator);
+        app.update();
+
+        // right away, without the rotators being rotated
+        app.world_mut().despawn(target);
+        for rotator in [warn, event] {
+            let rotate_to = app.world().get::<RotateTo>(rotator).unwrap();
+            assert_eq!(rotate_to.target, RotateTarget::default());
+            assert_eq!(rotate_to.target_offset, Vec3::Y);
+        }
+        assert!(app.world().get::<RotateTo>(remove).is_none());
+        assert!(app.world().get_entity(despawn).is_err());
+        let triggered = app.world().resource::<Triggered>();
+        assert_eq!((triggered.lost, triggered.missing), (4, 1));
+        app.update();
+        assert!(app.world().resource::<LostTargets>().0.is_empty());
+    }
+
+    #[test]
+    fn despawned_rotators_are_forgotten() {
+        let mut app = app();
+        let target = app.world_mut().spawn_empty().id();
+        let lost = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        let parent = app.world_mut().spawn_empty().id();
+        let broken = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(Vec3::X));
+        app.world_mut().entity_mut(broken).insert(ChildOf(parent));
+        app.update();
+        assert!(app.world().resource::<LostTargets>().0.contains(&lost));
+        let broken_hierarchies = app.world().resource::<BrokenHierarchies>();
+        assert!(broken_hierarchies.0.contains(&broken));
+
+        app.world_mut().despawn(lost);
+        app.world_mut().despawn(broken);
+        assert!(app.world().resource::<LostTargets>().0.is_empty());
+        assert!(app.world().resource::<BrokenHierarchies>().0.is_empty());
+    }
+
+    fn forward_of(app: &App, entity: Entity) -> Vec3 {
+        rotation_of(app, entity) * Vec3::NEG_Z
+    }
+
+    fn move_to(app: &mut App, entity: Entity, translation: Vec3) {
+        *app.world_mut().get_mut::<GlobalTransform>(entity).unwrap() =
+            GlobalTransform::from_translation(translation);
+    }
+
+    #[test]
+    fn weight_turns_the_same_part_of_the_way_every_frame() {
+        let mut app = app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(Vec3::new(11.0, 2.0, 3.0)), RotateWeight(0.5)),
+        );
+        for _ in 0..4 {
+            app.update();
+            let angle = rotation_of(&app, rotator).angle_between(Quat::IDENTITY);
+            assert!((angle - FRAC_PI_4).abs() < 1e-4, "{angle}");
+        }
+
+        // rotated by something else, like an animation, it is weighed from there
+        let animated = Quat::from_rotation_y(FRAC_PI_4);
+        app.world_mut()
+            .get_mut::<Transform>(rotator)
+            .unwrap()
+            .rotation = animated;
+        app.update();
+        app.update();
+        let angle = rotation_of(&app, rotator).angle_between(animated);
+        assert!((angle - 3.0 * FRAC_PI_8).abs() < 1e-4, "{angle}");
+    }
+
+    #[test]
+    fn engagement_range_freezes_or_returns_to_rest() {
+        #[derive(Resource, Default)]
+        struct Crossings(Vec<bool>);
+
+        let mut app = app();
+        app.init_resource::<Crossings>()
+            .add_observer(
+                |_: On<TargetEnteredRange>, mut crossings: ResMut<Crossings>| {
+                    crossings.0.push(true);
+                },
+            )
+            .add_observer(|_: On<TargetLeftRange>, mut crossings: ResMut<Crossings>| {
+                crossings.0.push(false);
+            });
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let frozen = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), EngagementRange::new(2.0, 20.0)),
+        );
+        let resting = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(target),
+                EngagementRange::new(2.0, 20.0).with_return_to_rest(Quat::IDENTITY),
+            ),
+        );
+        app.update();
+        for rotator in [frozen, resting] {
+            assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+        }
+
+        move_to(&mut app, target, Vec3::new(1.0, 2.0, 33.0));
+        app.update();
+        assert!(forward_of(&app, frozen).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(forward_of(&app, resting).abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        let state = app.world().get::<EngagementRangeState>(frozen).unwrap();
+        assert!(!state.in_range);
+        assert_eq!(
+            app.world().resource::<Crossings>().0,
+            [true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn field_of_view_only_tracks_targets_in_view() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(target),
+                FieldOfView {
+                    degrees: 90.0,
+                    reference: FieldOfViewReference::Parent,
+                },
+            ),
+        );
+        app.update();
+        // straight to the side, outside of the cone
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+        assert!(
+            !app.world()
+                .get::<FieldOfViewState>(rotator)
+                .unwrap()
+                .in_view
+        );
+
+        let seen = Vec3::new(5.0, 0.0, -10.0);
+        move_to(&mut app, target, Vec3::new(1.0, 2.0, 3.0) + seen);
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(seen.normalize(), 1e-5));
+        assert!(
+            app.world()
+                .get::<FieldOfViewState>(rotator)
+                .unwrap()
+                .in_view
+        );
+
+        // behind, it keeps facing where it last saw the target
+        move_to(&mut app, target, Vec3::new(1.0, 2.0, 13.0));
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(seen.normalize(), 1e-5));
+    }
+
+    #[test]
+    fn lod_rotates_far_rotators_less_often() {
+        let mut app = app();
+        let camera = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        app.insert_resource(
+            RotateLod::new(camera)
+                .with_band(10.0, 1)
+                .with_band(100.0, 3),
+        );
+        let mut spawn = |x: f32, bundle: Option<RotateLodBands>| {
+            let mut rotator = app.world_mut().spawn((
+                Transform::from_xyz(x, 0.0, 0.0),
+                GlobalTransform::from_xyz(x, 0.0, 0.0),
+                RotateTo::new(Dir3::X),
+            ));
+            if let Some(bands) = bundle {
+                rotator.insert(bands);
+            }
+            rotator.id()
+        };
+        let near = spawn(5.0, None);
+        let mid = spawn(50.0, None);
+        let far = spawn(500.0, None);
+        let own = spawn(500.0, Some(RotateLodBands::default().with_band(1000.0, 1)));
+        let turned = |app: &App, rotator| forward_of(app, rotator).abs_diff_eq(Vec3::X, 1e-5);
+
+        app.update();
+        assert!(turned(&app, near));
+        assert!(turned(&app, own));
+        app.update();
+        app.update();
+        assert!(turned(&app, mid));
+        assert!(!turned(&app, far));
+    }
+
+    #[test]
+    fn saccades_are_seeded() {
+        let glances = |seed| {
+            let mut app = timed_app();
+            let rotator = spawn_rotator(
+                &mut app,
+                Quat::IDENTITY,
+                (
+                    RotateTo::new(Vec3::new(1.0, 2.0, -7.0)),
+                    RotateSaccades {
+                        amplitude_degrees: 3.0,
+                        frequency: 4.0,
+                        jitter_degrees: 0.2,
+                        seed,
+                    },
+                ),
+            );
+            (0..30)
+                .map(|_| {
+                    app.update();
+                    rotation_of(&app, rotator)
+                })
+                .collect::<Vec<_>>()
+        };
+        let rotations = glances(7);
+        assert_eq!(rotations, glances(7));
+        assert_ne!(rotations, glances(8));
+        let degrees = rotations
+            .iter()
+            .map(|rotation| rotation.angle_between(Quat::IDENTITY).to_degrees());
+        assert!(degrees.clone().all(|degrees| degrees <= 3.3));
+        assert!(degrees.clone().any(|degrees| degrees > 0.5));
+    }
+
+    #[test]
+    fn returns_to_rest_once_tracking_stops() {
+        let mut app = timed_app();
+        let rest = Quat::from_rotation_y(0.5);
+        let rotator = spawn_rotator(
+            &mut app,
+            rest,
+            (
+                RotateTo::new(Vec3::new(11.0, 2.0, 3.0)),
+                RotateReturnToRest {
+                    duration: 1.0,
+                    ..Default::default()
+                },
+            ),
+        );
+        app.update();
+        let facing = rotation_of(&app, rotator);
+        assert!((facing * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+        let state = app.world().get::<RotateReturnToRestState>(rotator).unwrap();
+        assert_eq!(state.rest, Some(rest));
+
+        // turns back over the duration
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = false;
+        for _ in 0..5 {
+            app.update();
+        }
+        let halfway = rotation_of(&app, rotator);
+        assert!(halfway.angle_between(rest) > 0.1 && halfway.angle_between(facing) > 0.1);
+        for _ in 0..6 {
+            app.update();
+        }
+        assert!(rotation_of(&app, rotator).angle_between(rest) < 1e-4);
+
+        // and again once it no longer has a target
+        app.world_mut()
+            .get_mut::<RotateTo>(rotator)
+            .unwrap()
+            .enabled = true;
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+        app.world_mut().entity_mut(rotator).remove::<RotateTo>();
+        for _ in 0..11 {
+            app.update();
+        }
+        assert!(rotation_of(&app, rotator).angle_between(rest) < 1e-4);
+    }
+
+    #[test]
+    fn settled_rotator_is_not_rotated_again() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(1.0, 2.0, 13.0))
+            .id();
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        app.update();
+        let changed = |app: &App| {
+            let rotator = app.world().entity(rotator);
+            rotator.get_ref::<Transform>().unwrap().last_changed()
+        };
+        let settled = changed(&app);
+        app.update();
+        app.update();
+        assert_eq!(changed(&app), settled);
+
+        // something else rotating it is undone
+        app.world_mut()
+            .get_mut::<Transform>(rotator)
+            .unwrap()
+            .rotation = Quat::IDENTITY;
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::Z, 1e-5));
+        move_to(&mut app, target, Vec3::new(11.0, 2.0, 3.0));
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn still_rotator_is_left_alone_until_something_changes() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), AngleToTarget::default()),
+        );
+        let solved = |app: &App| {
+            let rotator = app.world().entity(rotator);
+            rotator.get_ref::<RotateSolution>().unwrap().last_changed()
+        };
+        app.update();
+        app.update();
+        let still = solved(&app);
+        app.update();
+        app.update();
+        assert_eq!(solved(&app), still);
+
+        // changing the config or the rotator moves it out of the archetype it was still in
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateOffset(Quat::from_rotation_y(0.5)));
+        app.update();
+        assert_ne!(solved(&app), still);
+        assert!(!forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+        app.world_mut().entity_mut(rotator).remove::<RotateOffset>();
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn still_rotator_follows_its_moved_parent_without_propagating() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(true));
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(10.0, 0.0, 0.0),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let parent = app
+            .world_mut()
+            .spawn((Transform::default(), GlobalTransform::default()))
+            .id();
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(target),
+                ChildOf(parent),
+            ))
+            .id();
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+
+        // the parent turns a quarter to the left, so the rotator turns to face behind it to keep
+        // facing the target
+        app.world_mut()
+            .get_mut::<Transform>(parent)
+            .unwrap()
+            .rotation = Quat::from_rotation_y(FRAC_PI_2);
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::Z, 1e-5));
+    }
+
+    #[test]
+    fn local_rotation_leaves_the_transform_alone() {
+        let mut app = app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(11.0, 2.0, 3.0)),
+                RotateLocalRotation::default(),
+            ),
+        );
+        app.update();
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+        let rotation = app.world().get::<RotateLocalRotation>(rotator).unwrap().0;
+        assert!((rotation.unwrap() * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn desired_rotation_leaves_the_transform_alone() {
+        let mut app = app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(11.0, 2.0, 3.0)),
+                DesiredLookRotation::default(),
+            ),
+        );
+        app.update();
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+        let look = app.world().get::<DesiredLookRotation>(rotator).unwrap();
+        assert!((look.world * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+        assert_eq!(look.local, look.world);
+    }
+
+    #[test]
+    fn settled_rotator_is_aligned_once_measured() {
+        #[derive(Resource, Default)]
+        struct Alignments(u32);
+
+        let mut app = app();
+        app.init_resource::<Alignments>().add_observer(
+            |_: On<Aligned>, mut alignments: ResMut<Alignments>| {
+                alignments.0 += 1;
+            },
+        );
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(11.0, 2.0, 3.0)),
+        );
+        app.update();
+        app.update();
+
+        // already facing the target, it is aligned as soon as it has a threshold
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(AlignmentThreshold::default());
+        app.update();
+        assert_eq!(app.world().resource::<Alignments>().0, 1);
+        app.update();
+        assert_eq!(app.world().resource::<Alignments>().0, 1);
+    }
+
+    #[test]
+    fn settled_rotator_measures_its_angle_to_target() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), RotateConeLimit::new(30.0)),
+        );
+        app.update();
+        app.update();
+
+        // the rotator and its target stay where they are, the angle is still measured
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(AngleToTarget::default());
+        app.update();
+        let angle = app.world().get::<AngleToTarget>(rotator).unwrap();
+        assert!((angle.degrees - 60.0).abs() < 1e-3, "{}", angle.degrees);
+        app.world_mut()
+            .entity_mut(rotator)
+            .remove::<RotateConeLimit>();
+        app.update();
+        let angle = app.world().get::<AngleToTarget>(rotator).unwrap();
+        assert!(angle.degrees < 1e-3, "{}", angle.degrees);
+    }
+
+    #[test]
+    fn settled_rotator_outputs_its_desired_rotation() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        app.update();
+        app.update();
+
+        // the rotator faces its static target, its desired rotation is still output
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(DesiredLookRotation::default());
+        app.update();
+        let look = app.world().get::<DesiredLookRotation>(rotator).unwrap();
+        assert!((look.world * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+
+        // only computing the rotation, it is output every time for a static target
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false).compute_only());
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(11.0, 2.0, 3.0))
+            .id();
+        let rotator = spawn_rotator(&mut app, Quat::IDENTITY, RotateTo::new(target));
+        app.update();
+        app.update();
+        *app.world_mut()
+            .get_mut::<DesiredLookRotation>(rotator)
+            .unwrap() = DesiredLookRotation::default();
+        app.update();
+        let look = app.world().get::<DesiredLookRotation>(rotator).unwrap();
+        assert!((look.world * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn settled_rotator_glances_once_it_has_saccades() {
+        let mut app = timed_app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            RotateTo::new(Vec3::new(1.0, 2.0, -7.0)),
+        );
+        app.update();
+        app.update();
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+
+        // the target stays in front of it, the glances still move it
+        app.world_mut().entity_mut(rotator).insert(RotateSaccades {
+            amplitude_degrees: 3.0,
+            frequency: 4.0,
+            jitter_degrees: 0.2,
+            seed: 7,
+        });
+        let degrees = (0..30)
+            .map(|_| {
+                app.update();
+                rotation_of(&app, rotator)
+                    .angle_between(Quat::IDENTITY)
+                    .to_degrees()
+            })
+            .collect::<Vec<_>>();
+        assert!(degrees.iter().any(|degrees| *degrees > 0.5));
+        let state = app.world().get::<RotateSaccadesState>(rotator).unwrap();
+        assert!(state.random.is_some());
+    }
+
+    #[test]
+    fn speed_limit_turns_at_most_its_rate() {
+        let mut app = timed_app();
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(Vec3::new(11.0, 2.0, 3.0)),
+                RotateSpeedLimit {
+                    max_degrees_per_second: 90.0,
+                },
+            ),
+        );
+        // a tenth of a second every update
+        for turned in 1..=10 {
+            app.update();
+            let angle = forward_of(&app, rotator).angle_between(Vec3::NEG_Z);
+            assert!((angle.to_degrees() - 9.0 * turned as f32).abs() < 1e-3);
+        }
+        app.update();
+        assert!(forward_of(&app, rotator).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn rotate_2d_only_turns_around_z() {
+        let mut app = app();
+        // the target is above the rotator, and in front of it along Z, which is ignored
+        let target = Vec3::new(1.0, 7.0, -10.0);
+        let right = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), Rotate2D::default()),
+        );
+        let up = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(target),
+                Rotate2D {
+                    angle_offset_degrees: 90.0,
+                },
+            ),
+        );
+        app.update();
+        let rotation = rotation_of(&app, right);
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 1e-5));
+        assert!((rotation * Vec3::X).abs_diff_eq(Vec3::Y, 1e-5));
+        assert!(rotation_of(&app, up).abs_diff_eq(Quat::IDENTITY, 1e-5));
+    }
+
+    #[test]
+    fn axis_lock_only_turns_around_its_axis() {
+        let mut app = app();
+        // above the rotator, to its right and in front of it
+        let target = Vec3::new(6.0, 7.0, -2.0);
+        let yaw = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), AxisLock::Yaw),
+        );
+        let pitch = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(target), AxisLock::Pitch),
+        );
+        app.update();
+        let expected = Vec3::new(1.0, 0.0, -1.0).normalize();
+        assert!(forward_of(&app, yaw).abs_diff_eq(expected, 1e-5));
+        let expected = Vec3::new(0.0, 1.0, -1.0).normalize();
+        assert!(forward_of(&app, pitch).abs_diff_eq(expected, 1e-5));
+        assert!((rotation_of(&app, pitch) * Vec3::X).abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn rotators_in_a_cycle_rotate_from_where_the_others_were() {
+        let turn = |a_first: bool| {
+            let mut app = App::new();
+            app.add_plugins(RotateTowardsPlugin::new(true));
+            let mut spawn = |translation: Vec3| {
+                let transform = Transform::from_translation(translation);
+                let entity = app
+                    .world_mut()
+                    .spawn((transform, GlobalTransform::from(transform)));
+                entity.id()
+            };
+            let (a, b) = if a_first {
+                let a = spawn(Vec3::ZERO);
+                (a, spawn(Vec3::new(0.0, 0.0, -10.0)))
+            } else {
+                let b = spawn(Vec3::new(0.0, 0.0, -10.0));
+                (spawn(Vec3::ZERO), b)
+            };
+            // each aims to the right of the other, which moves as the other turns
+            for (rotator, target) in [(a, b), (b, a)] {
+                app.world_mut()
+                    .entity_mut(rotator)
+                    .insert(RotateTo::new(target).with_target_offset(Vec3::X));
+            }
+            app.update();
+            (forward_of(&app, a), forward_of(&app, b))
+        };
+        let (a, b) = turn(true);
+        assert!(a.abs_diff_eq(Vec3::new(1.0, 0.0, -10.0).normalize(), 1e-5));
+        assert!(b.abs_diff_eq(Vec3::new(1.0, 0.0, 10.0).normalize(), 1e-5));
+        let (a_again, b_again) = turn(false);
+        assert!(a.abs_diff_eq(a_again, 1e-6) && b.abs_diff_eq(b_again, 1e-6));
+    }
+
+    #[test]
+    fn deadband_keeps_rotation_until_the_target_leaves_it() {
+        let mut app = app();
+        let towards = |degrees: f32| {
+            let yaw = degrees.to_radians();
+            Vec3::new(1.0, 2.0, 3.0) + 10.0 * Vec3::new(-ops::sin(yaw), 0.0, -ops::cos(yaw))
+        };
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (RotateTo::new(towards(0.3)), RotateDeadband::default()),
+        );
+        app.update();
+        let changed = app
+            .world()
+            .entity(rotator)
+            .get_ref::<Transform>()
+            .unwrap()
+            .last_changed();
+        assert_eq!(rotation_of(&app, rotator), Quat::IDENTITY);
+        app.update();
+        let transform = app.world().entity(rotator).get_ref::<Transform>().unwrap();
+        assert_eq!(transform.last_changed(), changed);
+
+        // outside the deadband it turns all the way
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateTo::new(towards(10.0)));
+        app.update();
+        let expected = (towards(10.0) - Vec3::new(1.0, 2.0, 3.0)).normalize();
+        assert!(forward_of(&app, rotator).abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn axis_lock_keeps_its_turn_direction_behind_it() {
+        let mut app = timed_app();
+        let towards = |degrees: f32| {
+            let yaw = degrees.to_radians();
+            Vec3::new(1.0, 2.0, 3.0) + 10.0 * Vec3::new(-ops::sin(yaw), 0.0, -ops::cos(yaw))
+        };
+        let yaw_of = |app: &App, rotator| {
+            let forward = forward_of(app, rotator);
+            ops::atan2(-forward.x, -forward.z).to_degrees()
+        };
+        let rotator = spawn_rotator(
+            &mut app,
+            Quat::IDENTITY,
+            (
+                RotateTo::new(towards(175.0)),
+                AxisLock::Yaw,
+                // 9 degrees each update
+                RotateSpeedLimit {
+                    max_degrees_per_second: 90.0,
+                },
+            ),
+        );
+        app.update();
+        assert!((yaw_of(&app, rotator) - 9.0).abs() < 1e-3);
+
+        // just past behind it, it keeps turning counterclockwise
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateTo::new(towards(194.0)));
+        app.update();
+        assert!((yaw_of(&app, rotator) - 18.0).abs() < 1e-3);
+
+        // clearly past behind it, it turns the short way
+        app.world_mut()
+            .entity_mut(rotator)
+            .insert(RotateTo::new(towards(210.0)));
+        app.update();
+        assert!((yaw_of(&app, rotator) - 9.0).abs() < 1e-3);
+    }
+}
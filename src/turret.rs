@@ -0,0 +1,240 @@
+use bevy_ecs::{entity::MapEntities, prelude::*, reflect::ReflectMapEntities, system::SystemParam};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    components::{AimLead, RotateSpeedLimit, RotateTarget, TargetVelocity},
+    math::calculate_local_rotation_around_axis,
+    systems::ResolvedTarget,
+};
+
+#[derive(Clone, Copy, Component, Debug, PartialEq, Reflect, MapEntities)]
+#[reflect(Component, Debug, Default, PartialEq, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Aims a turret made of two entities, this base that only yaws, around `Vec3::Y` in the space of
+/// its parent, and a barrel child of it that only pitches, around its `Vec3::X`, so that the
+/// forward direction of the barrel points at the target
+///
+/// Both are solved together, the pitch of the barrel is calculated from where the base is turned to,
+/// so the barrel does not lag behind the base. Add [`AimLead`] to the base to aim ahead of a
+/// moving target.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// fn spawn_turret(mut commands: Commands, enemy: Entity) {
+///     let barrel = commands.spawn(Transform::from_xyz(0.0, 1.0, 0.0)).id();
+///     commands
+///         .spawn(
+///             TurretAim::new(enemy, barrel)
+///                 .with_yaw_speed_limit(90.0)
+///                 .with_pitch_speed_limit(45.0),
+///         )
+///         .add_child(barrel);
+/// }
+/// ```
+pub struct TurretAim {
+    /// what to aim at, see [`RotateTarget`]
+    #[entities]
+    pub target: RotateTarget,
+    /// the child of the base that pitches
+    #[entities]
+    pub barrel: Entity,
+    /// limits how fast the base yaws
+    pub yaw_speed_limit: Option<RotateSpeedLimit>,
+    /// limits how fast the barrel pitches
+    pub pitch_speed_limit: Option<RotateSpeedLimit>,
+}
+
+/// Aims [`Entity::PLACEHOLDER`] at [`Entity::PLACEHOLDER`], set the target and barrel before use
+impl Default for TurretAim {
+    fn default() -> Self {
+        Self::new(Entity::PLACEHOLDER, Entity::PLACEHOLDER)
+    }
+}
+
+impl TurretAim {
+    /// Aims the `barrel` child of the turret at the target
+    pub fn new(target: impl Into<RotateTarget>, barrel: Entity) -> Self {
+        Self {
+            target: target.into(),
+            barrel,
+            yaw_speed_limit: None,
+            pitch_speed_limit: None,
+        }
+    }
+
+    /// Limits how fast the base yaws
+    pub fn with_yaw_speed_limit(mut self, max_degrees_per_second: f32) -> Self {
+        self.yaw_speed_limit = Some(RotateSpeedLimit {
+            max_degrees_per_second,
+        });
+        self
+    }
+
+    /// Limits how fast the barrel pitches
+    pub fn with_pitch_speed_limit(mut self, max_degrees_per_second: f32) -> Self {
+        self.pitch_speed_limit = Some(RotateSpeedLimit {
+            max_degrees_per_second,
+        });
+        self
+    }
+}
+
+/// What [`aim_turrets`] reads, before writing the new rotations
+#[derive(SystemParam)]
+pub(crate) struct TurretTransforms<'w, 's> {
+    trans_helper: TransformHelper<'w, 's>,
+    transforms: Query<'w, 's, &'static Transform>,
+}
+
+/// Yaws the base, and pitches the barrel, of every [`TurretAim`]
+pub(crate) fn aim_turrets(
+    turrets: Query<(Entity, &TurretAim, Option<&ChildOf>, Option<&AimLead>)>,
+    velocities: Query<&TargetVelocity>,
+    mut params: ParamSet<(TurretTransforms, Query<&mut Transform>)>,
+    time: Option<Res<Time>>,
+    mut rotations: Local<Vec<(Entity, Quat)>>,
+) {
+    let delta_secs = time.as_deref().map(Time::delta_secs);
+    let limit = |speed_limit: Option<RotateSpeedLimit>, current: Quat, desired: Quat| match (
+        speed_limit,
+        delta_secs,
+    ) {
+        (Some(speed_limit), Some(delta_secs)) => speed_limit.limit(current, desired, delta_secs),
+        _ => desired,
+    };
+
+    // the global transforms are computed first, since they depend on the transforms being written
+    let TurretTransforms {
+        trans_helper,
+        transforms,
+    } = params.p0();
+    for (base, turret, child_of, aim_lead) in turrets.iter() {
+        let global_transform = |entity| trans_helper.compute_global_transform(entity).ok();
+        let parent_gt = child_of.and_then(|child_of| global_transform(child_of.parent()));
+        let (Ok(base_t), Ok(barrel_t), Some(base_gt)) = (
+            transforms.get(base),
+            transforms.get(turret.barrel),
+            global_transform(base),
+        ) else {
+            continue;
+        };
+        let target = match turret.target {
+            RotateTarget::Entity(entity) => {
+                let Some(target_gt) = global_transform(entity) else {
+                    continue;
+                };
+                let position = target_gt.translation();
+                // aim ahead of a moving target, from where the projectiles leave the barrel
+                let position = match (
+                    aim_lead,
+                    velocities.get(entity),
+                    global_transform(turret.barrel),
+                ) {
+                    (Some(aim_lead), Ok(velocity), Some(barrel_gt)) => {
+                        aim_lead.predict(barrel_gt.translation(), position, velocity.0)
+                    }
+                    _ => position,
+                };
+                ResolvedTarget::Point(position, None)
+            }
+            RotateTarget::Point(point) => ResolvedTarget::Point(point, None),
+            RotateTarget::Direction(direction) => ResolvedTarget::Direction(direction, None),
+        };
+
+        let Some(yaw) = calculate_local_rotation_around_axis(
+            target.direction_from(&base_gt),
+            parent_gt.as_ref(),
+            Dir3::Y,
+        ) else {
+            continue;
+        };
+        let yaw = limit(turret.yaw_speed_limit, base_t.rotation, yaw);
+
+        // where the base and barrel will be, once the base is turned
+        let yawed_base_gt = parent_gt.unwrap_or_default() * base_t.with_rotation(yaw);
+        let yawed_barrel_gt = yawed_base_gt * *barrel_t;
+        let Some(pitch) = calculate_local_rotation_around_axis(
+            target.direction_from(&yawed_barrel_gt),
+            Some(&yawed_base_gt),
+            Dir3::X,
+        ) else {
+            continue;
+        };
+        let pitch = limit(turret.pitch_speed_limit, barrel_t.rotation, pitch);
+
+        rotations.push((base, yaw));
+        rotations.push((turret.barrel, pitch));
+    }
+
+    let mut transforms = params.p1();
+    for (entity, rotation) in rotations.drain(..) {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+
+    use super::*;
+    use crate::RotateTowardsPlugin;
+
+    /// Spawns a turret at the origin, with its barrel a unit above it
+    fn spawn_turret(app: &mut App, turret: impl FnOnce(Entity) -> TurretAim) -> (Entity, Entity) {
+        let barrel = app
+            .world_mut()
+            .spawn(Transform::from_xyz(0.0, 1.0, 0.0))
+            .id();
+        let base = app
+            .world_mut()
+            .spawn((Transform::default(), turret(barrel)))
+            .add_child(barrel)
+            .id();
+        (base, barrel)
+    }
+
+    #[test]
+    fn base_yaws_and_barrel_pitches() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let target = Vec3::new(10.0, 5.0, 0.0);
+        let (base, barrel) = spawn_turret(&mut app, |barrel| TurretAim::new(target, barrel));
+        app.update();
+        let base_t = *app.world().get::<Transform>(base).unwrap();
+        assert!(base_t.forward().abs_diff_eq(Vec3::X, 1e-5));
+        // the barrel only turns around its x axis
+        let barrel_t = *app.world().get::<Transform>(barrel).unwrap();
+        let (axis, _) = barrel_t.rotation.to_axis_angle();
+        assert!(axis.abs().abs_diff_eq(Vec3::X, 1e-5), "{axis}");
+        let barrel_forward = base_t.rotation * barrel_t.forward();
+        let expected = (target - Vec3::Y).normalize();
+        assert!(
+            barrel_forward.abs_diff_eq(expected, 1e-5),
+            "{barrel_forward}"
+        );
+    }
+
+    #[test]
+    fn yaw_speed_limit() {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let mut time = Time::<()>::default();
+        time.advance_by(core::time::Duration::from_millis(250));
+        app.insert_resource(time);
+        let (base, _) = spawn_turret(&mut app, |barrel| {
+            TurretAim::new(Vec3::X * 10.0, barrel).with_yaw_speed_limit(90.0)
+        });
+        app.update();
+        let forward = app.world().get::<Transform>(base).unwrap().forward();
+        let degrees = forward.angle_between(Vec3::NEG_Z).to_degrees();
+        assert!((degrees - 22.5).abs() < 0.01, "{degrees}");
+    }
+}
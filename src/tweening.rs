@@ -0,0 +1,122 @@
+use core::time::Duration;
+
+use bevy_transform::prelude::*;
+use bevy_tweening::{lens::TransformRotationLens, EaseMethod, Tween};
+
+use crate::components::DesiredLookRotation;
+
+/// A [`Tween`] turning the [`Transform`] of a rotator from its current rotation to its
+/// [`DesiredLookRotation`] over `duration`, with the `bevy_tweening` feature
+///
+/// The rotation is solved once, so the tween turns towards where the target was when it was
+/// made, for "turn to face it, then carry on" behaviours in a tween pipeline. The
+/// [`DesiredLookRotation`] keeps the rotation systems from turning the [`Transform`] themselves,
+/// and is published once they run, make the tween after the
+/// [`RotateTowardsSystems::Rotate`](crate::RotateTowardsSystems::Rotate) set.
+///
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use bevy_math::curve::EaseFunction;
+/// # use bevy_tweening::{TweenAnim, TweeningPlugin};
+/// # use core::time::Duration;
+/// /// Turns to face its target over half a second, then stops tracking it
+/// #[derive(Component)]
+/// #[require(DesiredLookRotation)]
+/// struct TurnToFace;
+///
+/// fn turn_to_face(
+///     mut commands: Commands,
+///     rotators: Query<
+///         (Entity, &Transform, &DesiredLookRotation),
+///         (With<TurnToFace>, Changed<DesiredLookRotation>),
+///     >,
+/// ) {
+///     for (entity, transform, desired) in rotators.iter() {
+///         let tween = look_rotation_tween(
+///             transform,
+///             desired,
+///             EaseFunction::QuadraticInOut,
+///             Duration::from_millis(500),
+///         );
+///         commands
+///             .entity(entity)
+///             .remove::<(RotateTo, TurnToFace)>()
+///             .insert(TweenAnim::new(tween));
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins((TweeningPlugin, RotateTowardsPlugin::default()))
+///     .add_systems(PostUpdate, turn_to_face.after(RotateTowardsSystems::Rotate));
+/// ```
+pub fn look_rotation_tween(
+    transform: &Transform,
+    desired: &DesiredLookRotation,
+    ease_method: impl Into<EaseMethod>,
+    duration: Duration,
+) -> Tween {
+    Tween::new(
+        ease_method,
+        duration,
+        TransformRotationLens {
+            start: transform.rotation,
+            end: desired.local,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_math::curve::EaseFunction;
+    use bevy_math::prelude::*;
+    use bevy_time::prelude::*;
+    use bevy_tweening::{TweenAnim, TweeningPlugin};
+
+    use super::*;
+    use crate::{components::RotateTo, RotateTowardsPlugin};
+
+    #[test]
+    fn tween_turns_to_the_desired_rotation() {
+        let mut app = App::new();
+        app.add_plugins((RotateTowardsPlugin::new(false), TweeningPlugin));
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        app.insert_resource(time);
+        let rotator = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateTo::new(Vec3::X),
+                DesiredLookRotation::default(),
+            ))
+            .id();
+        app.update();
+        let transform = *app.world().get::<Transform>(rotator).unwrap();
+        let desired = *app.world().get::<DesiredLookRotation>(rotator).unwrap();
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+
+        let tween = look_rotation_tween(
+            &transform,
+            &desired,
+            EaseFunction::Linear,
+            Duration::from_millis(200),
+        );
+        app.world_mut()
+            .entity_mut(rotator)
+            .remove::<RotateTo>()
+            .insert(TweenAnim::new(tween));
+        app.update();
+        let rotation = app.world().get::<Transform>(rotator).unwrap().rotation;
+        let halfway = Quat::IDENTITY.slerp(desired.local, 0.5);
+        assert!(rotation.angle_between(halfway) < 1e-3, "{rotation}");
+        app.update();
+        let rotation = app.world().get::<Transform>(rotator).unwrap().rotation;
+        assert!(rotation.angle_between(desired.local) < 1e-3, "{rotation}");
+        assert!((rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::X, 1e-5));
+    }
+}
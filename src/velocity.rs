@@ -0,0 +1,443 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    component::Mutable,
+    prelude::*,
+    schedule::{InternedScheduleLabel, ScheduleLabel},
+};
+use bevy_math::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::prelude::*;
+use bevy_transform::prelude::*;
+
+use crate::{
+    components::{RotateAngularVelocity, RotateTorqueState, TargetVelocity},
+    plugin::RotateTowardsSystems,
+};
+
+/// A component that knows the world space velocity of its entity, like the velocity of a rigid body
+/// of a physics engine, or of your own movement code
+///
+/// Add a [`VelocitySourcePlugin`] for it, to keep the [`TargetVelocity`] that [`AimLead`](crate::AimLead)
+/// aims with up to date, without depending on a physics engine here.
+///
+/// With the `avian` feature, the `RotateTowardsAvianPlugin` wires up the components of Avian.
+/// The components of other physics engines are wired up with a line each, with
+/// [`VelocitySourcePlugin::new`], [`AngularVelocitySinkPlugin`], [`TorqueSinkPlugin`] and a
+/// [`LineOfSightBackend`](crate::LineOfSightBackend) casting its rays.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_app::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// #[derive(Component)]
+/// struct Velocity(Vec3);
+///
+/// impl VelocitySource for Velocity {
+///     fn velocity(&self) -> Vec3 {
+///         self.0
+///     }
+/// }
+///
+/// App::new().add_plugins((
+///     RotateTowardsPlugin::default(),
+///     VelocitySourcePlugin::<Velocity>::default(),
+/// ));
+/// ```
+pub trait VelocitySource: Component {
+    /// The world space velocity, in units per second
+    fn velocity(&self) -> Vec3;
+}
+
+/// Plugin that requires a [`TargetVelocity`] on every entity with the component `T`,
+/// and copies the velocity of it into it, before the rotation systems run
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`VelocitySourcePlugin::in_schedule`].
+///
+/// Components of other crates, like the velocity of a physics engine, can not implement
+/// [`VelocitySource`], use [`VelocitySourcePlugin::new`] for them instead
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # mod physics {
+/// #     #[derive(bevy_ecs::component::Component)]
+/// #     pub struct LinearVelocity(pub bevy_math::Vec3);
+/// # }
+/// use physics::LinearVelocity;
+///
+/// App::new().add_plugins(VelocitySourcePlugin::new(|velocity: &LinearVelocity| velocity.0));
+/// ```
+pub struct VelocitySourcePlugin<T: Component> {
+    /// the schedule the velocities are copied in
+    schedule: InternedScheduleLabel,
+    /// reads the velocity from the component
+    velocity: fn(&T) -> Vec3,
+}
+
+impl<T: VelocitySource> Default for VelocitySourcePlugin<T> {
+    fn default() -> Self {
+        Self::new(T::velocity)
+    }
+}
+
+impl<T: Component> VelocitySourcePlugin<T> {
+    /// Copies the velocity, that `velocity` reads from `T`
+    pub fn new(velocity: fn(&T) -> Vec3) -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            velocity,
+        }
+    }
+
+    /// Copies the velocities in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<T: Component> Plugin for VelocitySourcePlugin<T> {
+    fn build(&self, app: &mut App) {
+        let velocity = self.velocity;
+        app.register_required_components::<T, TargetVelocity>();
+        app.add_systems(
+            self.schedule,
+            (move |targets: Query<(&T, &mut TargetVelocity), Changed<T>>| {
+                sync_target_velocity(targets, velocity);
+            })
+            .in_set(RotateTowardsSystems::Prepare),
+        );
+    }
+}
+
+/// Copies the velocity of every `T` into its [`TargetVelocity`]
+fn sync_target_velocity<T: Component>(
+    mut targets: Query<(&T, &mut TargetVelocity), Changed<T>>,
+    velocity: fn(&T) -> Vec3,
+) {
+    for (source, mut target_velocity) in targets.iter_mut() {
+        target_velocity.set_if_neq(TargetVelocity(velocity(source)));
+    }
+}
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[require(TargetVelocity, EstimateVelocityState)]
+/// Estimates the [`TargetVelocity`] of the entity from how its [`GlobalTransform`] moves,
+/// before the rotation systems run, for entities without a [`VelocitySource`]
+///
+/// The estimate is from the global transforms of the last two frames, so it lags a frame behind.
+pub struct EstimateVelocity;
+
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Where the entity of an [`EstimateVelocity`] was the last time its velocity was estimated
+pub struct EstimateVelocityState {
+    pub previous_translation: Option<Vec3>,
+}
+
+/// Estimates the velocity of every [`EstimateVelocity`] from how far it moved since last time
+pub(crate) fn estimate_velocities(
+    mut entities: Query<
+        (
+            &GlobalTransform,
+            &mut TargetVelocity,
+            &mut EstimateVelocityState,
+        ),
+        With<EstimateVelocity>,
+    >,
+    time: Option<Res<Time>>,
+) {
+    let delta_secs = time.as_deref().map_or(0.0, Time::delta_secs);
+    for (gt, mut velocity, mut state) in entities.iter_mut() {
+        let translation = gt.translation();
+        if let Some(previous) = state.previous_translation {
+            if delta_secs > 0.0 {
+                velocity.set_if_neq(TargetVelocity((translation - previous) / delta_secs));
+            }
+        }
+        state.set_if_neq(EstimateVelocityState {
+            previous_translation: Some(translation),
+        });
+    }
+}
+
+/// Plugin that copies the [`RotateAngularVelocity`] of every entity with the component `T` into it,
+/// with `write`, after the rotation systems run, like into the angular velocity of a kinematic
+/// rigid body
+///
+/// By default it runs in [`PostUpdate`], run it in the same schedule as the
+/// [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with [`AngularVelocitySinkPlugin::in_schedule`].
+///
+/// It is how the angular velocity reaches a physics engine, with the `avian` feature the
+/// `RotateTowardsAvianPlugin` adds one for the bodies of Avian, see [`VelocitySource`].
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # mod physics {
+/// #     #[derive(bevy_ecs::component::Component)]
+/// #     pub struct AngularVelocity(pub bevy_math::Vec3);
+/// # }
+/// use physics::AngularVelocity;
+///
+/// App::new().add_plugins(AngularVelocitySinkPlugin::new(
+///     |body: &mut AngularVelocity, velocity| body.0 = velocity,
+/// ));
+/// ```
+pub struct AngularVelocitySinkPlugin<T: Component<Mutability = Mutable>> {
+    /// the schedule the angular velocities are copied in
+    schedule: InternedScheduleLabel,
+    /// writes the angular velocity into the component
+    write: fn(&mut T, Vec3),
+}
+
+impl<T: Component<Mutability = Mutable>> AngularVelocitySinkPlugin<T> {
+    /// Copies the angular velocity into `T` with `write`
+    pub fn new(write: fn(&mut T, Vec3)) -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            write,
+        }
+    }
+
+    /// Copies the angular velocities in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<T: Component<Mutability = Mutable>> Plugin for AngularVelocitySinkPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let write = self.write;
+        app.add_systems(
+            self.schedule,
+            (move |bodies: Query<
+                (&RotateAngularVelocity, &mut T),
+                Changed<RotateAngularVelocity>,
+            >| {
+                sync_angular_velocity(bodies, write);
+            })
+            .after(RotateTowardsSystems::Rotate),
+        );
+    }
+}
+
+/// Copies every [`RotateAngularVelocity`] into its `T`
+fn sync_angular_velocity<T: Component<Mutability = Mutable>>(
+    mut bodies: Query<(&RotateAngularVelocity, &mut T), Changed<RotateAngularVelocity>>,
+    write: fn(&mut T, Vec3),
+) {
+    for (angular_velocity, mut body) in bodies.iter_mut() {
+        write(&mut body, angular_velocity.0);
+    }
+}
+
+/// Plugin that applies the torque of every [`RotateTorque`](crate::RotateTorque) on an entity with the component `T`,
+/// with `apply`, after the rotation systems run, like to the external torque of a dynamic rigid body
+///
+/// The torque is applied every time the plugin runs, it is zero while the entity stops tracking its
+/// target, so the body is no longer pushed. It runs by default in [`PostUpdate`], run it in the
+/// same schedule as the [`RotateTowardsPlugin`](crate::RotateTowardsPlugin) with
+/// [`TorqueSinkPlugin::in_schedule`].
+///
+/// It is how the torque reaches a physics engine, with the `avian` feature the
+/// `RotateTowardsAvianPlugin` adds one for the bodies of Avian, see [`VelocitySource`].
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_mod_lookat::prelude::*;
+/// # mod physics {
+/// #     #[derive(bevy_ecs::component::Component)]
+/// #     pub struct ExternalTorque(pub bevy_math::Vec3);
+/// # }
+/// use physics::ExternalTorque;
+///
+/// App::new().add_plugins(TorqueSinkPlugin::new(
+///     |body: &mut ExternalTorque, torque| body.0 = torque,
+/// ));
+/// ```
+pub struct TorqueSinkPlugin<T: Component<Mutability = Mutable>> {
+    /// the schedule the torques are applied in
+    schedule: InternedScheduleLabel,
+    /// applies the torque to the component
+    apply: fn(&mut T, Vec3),
+}
+
+impl<T: Component<Mutability = Mutable>> TorqueSinkPlugin<T> {
+    /// Applies the torque to `T` with `apply`
+    pub fn new(apply: fn(&mut T, Vec3)) -> Self {
+        Self {
+            schedule: PostUpdate.intern(),
+            apply,
+        }
+    }
+
+    /// Applies the torques in the given schedule instead of [`PostUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+}
+
+impl<T: Component<Mutability = Mutable>> Plugin for TorqueSinkPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let apply = self.apply;
+        app.add_systems(
+            self.schedule,
+            (move |bodies: Query<(&RotateTorqueState, &mut T)>| {
+                apply_torque(bodies, apply);
+            })
+            .after(RotateTowardsSystems::Rotate),
+        );
+    }
+}
+
+/// Applies the torque of every [`RotateTorqueState`] to its `T`
+fn apply_torque<T: Component<Mutability = Mutable>>(
+    mut bodies: Query<(&RotateTorqueState, &mut T)>,
+    apply: fn(&mut T, Vec3),
+) {
+    for (state, mut body) in bodies.iter_mut() {
+        apply(&mut body, state.torque);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::{
+        components::{RotateTo, RotateToDirection, RotateTorque},
+        RotateTowardsPlugin,
+    };
+
+    #[derive(Component, Default)]
+    struct Body(Vec3);
+
+    impl VelocitySource for Body {
+        fn velocity(&self) -> Vec3 {
+            self.0
+        }
+    }
+
+    /// An app whose time advances by a tenth of a second every update
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RotateTowardsPlugin::new(false));
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+        app.insert_resource(time);
+        app
+    }
+
+    fn spawn_rotator(app: &mut App, bundle: impl Bundle) -> Entity {
+        app.world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RotateToDirection::new(Dir3::X),
+                Body::default(),
+                bundle,
+            ))
+            .id()
+    }
+
+    #[test]
+    fn velocity_sources_are_copied() {
+        let mut app = app();
+        app.add_plugins(VelocitySourcePlugin::<Body>::default());
+        let target = app.world_mut().spawn(Body(Vec3::X)).id();
+        app.update();
+        let velocity = app.world().get::<TargetVelocity>(target).unwrap().0;
+        assert_eq!(velocity, Vec3::X);
+    }
+
+    #[test]
+    fn velocities_are_estimated() {
+        let mut app = app();
+        let target = app
+            .world_mut()
+            .spawn((GlobalTransform::IDENTITY, EstimateVelocity))
+            .id();
+        app.update();
+        *app.world_mut().get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(0.5, 0.0, 0.0);
+        app.update();
+        let velocity = app.world().get::<TargetVelocity>(target).unwrap().0;
+        assert!(velocity.abs_diff_eq(Vec3::X * 5.0, 1e-4), "{velocity}");
+    }
+
+    #[test]
+    fn angular_velocity_is_copied_into_the_sink() {
+        let mut app = app();
+        app.add_plugins(AngularVelocitySinkPlugin::new(
+            |body: &mut Body, velocity| {
+                body.0 = velocity;
+            },
+        ));
+        let rotator = spawn_rotator(&mut app, RotateAngularVelocity::default());
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(rotator).unwrap().rotation,
+            Quat::IDENTITY
+        );
+        // turning right a quarter turn within the frame, around -Y
+        let velocity = app.world().get::<RotateAngularVelocity>(rotator).unwrap().0;
+        let expected = Vec3::NEG_Y * core::f32::consts::FRAC_PI_2 * 10.0;
+        assert!(velocity.abs_diff_eq(expected, 1e-3), "{velocity}");
+        assert_eq!(app.world().get::<Body>(rotator).unwrap().0, velocity);
+    }
+
+    #[test]
+    fn torque_is_applied_to_the_sink() {
+        let mut app = app();
+        app.add_plugins(TorqueSinkPlugin::new(|body: &mut Body, torque| {
+            body.0 = torque;
+        }));
+        let rotator = spawn_rotator(
+            &mut app,
+            RotateTorque {
+                stiffness: 2.0,
+                damping: 1.0,
+                max_torque: 1.0,
+            },
+        );
+        app.update();
+        assert_eq!(
+            app.world().get::<Transform>(rotator).unwrap().rotation,
+            Quat::IDENTITY
+        );
+        // clamped to the max torque
+        let torque = app
+            .world()
+            .get::<RotateTorqueState>(rotator)
+            .unwrap()
+            .torque;
+        assert!(torque.abs_diff_eq(Vec3::NEG_Y, 1e-4), "{torque}");
+        assert_eq!(app.world().get::<Body>(rotator).unwrap().0, torque);
+
+        // the sink stops pushing the body once the rotator stops tracking
+        app.world_mut()
+            .entity_mut(rotator)
+            .remove::<RotateToDirection>()
+            .insert(RotateTo {
+                enabled: false,
+                ..RotateTo::new(Dir3::X)
+            });
+        app.update();
+        assert_eq!(app.world().get::<Body>(rotator).unwrap().0, Vec3::ZERO);
+    }
+}